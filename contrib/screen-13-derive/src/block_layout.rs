@@ -0,0 +1,109 @@
+use {
+    super::is_repr_c,
+    proc_macro2::{Span, TokenStream},
+    quote::quote,
+    syn::{Attribute, Data, DeriveInput, Error, Index, Lit, LitStr, Meta, NestedMeta, Result},
+};
+
+#[derive(Default)]
+struct FieldAttrs {
+    name: Option<LitStr>,
+    skip: bool,
+}
+
+impl FieldAttrs {
+    fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut res = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("block")) {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(Error::new_spanned(meta, "expected #[block(..)]")),
+            };
+
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => res.skip = true,
+                    NestedMeta::Meta(Meta::NameValue(name_value))
+                        if name_value.path.is_ident("name") =>
+                    {
+                        match name_value.lit {
+                            Lit::Str(lit) => res.name = Some(lit),
+                            lit => return Err(Error::new_spanned(lit, "expected a member name")),
+                        }
+                    }
+                    nested => {
+                        return Err(Error::new_spanned(
+                            nested,
+                            "expected `name = \"..\"` or `skip`",
+                        ))
+                    }
+                }
+            }
+        }
+
+        Ok(res)
+    }
+}
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "BlockLayout can only be derived for structs",
+            ))
+        }
+    };
+
+    if !is_repr_c(&input.attrs) {
+        return Err(Error::new(
+            Span::call_site(),
+            "BlockLayout requires a #[repr(C)] struct",
+        ));
+    }
+
+    let mut block_fields = vec![];
+
+    for (idx, field) in fields.iter().enumerate() {
+        let attrs = FieldAttrs::parse(&field.attrs)?;
+
+        if attrs.skip {
+            continue;
+        }
+
+        let (member, name) = match &field.ident {
+            Some(ident) => (quote!(#ident), ident.to_string()),
+            None => {
+                let idx = Index::from(idx);
+                (quote!(#idx), idx.index.to_string())
+            }
+        };
+        let name = attrs
+            .name
+            .unwrap_or_else(|| LitStr::new(&name, Span::call_site()));
+
+        block_fields.push(quote! {
+            (
+                #name,
+                unsafe { ::std::ptr::addr_of!((*base).#member) as usize - base as usize } as u32,
+            )
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::screen_13::driver::BlockLayout for #ident #ty_generics #where_clause {
+            fn block_fields() -> ::std::vec::Vec<(&'static str, u32)> {
+                // Field offsets are found without creating a reference to uninitialized data
+                let uninit = ::std::mem::MaybeUninit::<Self>::uninit();
+                let base = uninit.as_ptr();
+
+                ::std::vec![#(#block_fields),*]
+            }
+        }
+    })
+}
@@ -0,0 +1,51 @@
+mod block_layout;
+mod shader_bindings;
+mod vertex;
+
+use {
+    proc_macro::TokenStream,
+    syn::{parse_macro_input, Attribute, Meta, NestedMeta},
+};
+
+/// Implements `screen_13::driver::BlockLayout` for a `#[repr(C)]` struct.
+///
+/// See the crate `README.md` for the supported `#[block(..)]` field attributes.
+#[proc_macro_derive(BlockLayout, attributes(block))]
+pub fn derive_block_layout(input: TokenStream) -> TokenStream {
+    block_layout::derive(parse_macro_input!(input))
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Implements `screen_13::graph::ShaderBindings` (and `ShaderDescriptors`) for a struct of render
+/// graph nodes.
+///
+/// See the crate `README.md` for the supported `#[descriptor(..)]` field attributes.
+#[proc_macro_derive(ShaderBindings, attributes(descriptor))]
+pub fn derive_shader_bindings(input: TokenStream) -> TokenStream {
+    shader_bindings::derive(parse_macro_input!(input))
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Implements `screen_13::driver::Vertex` for a `#[repr(C)]` struct.
+///
+/// See the crate `README.md` for the supported `#[vertex(..)]` field attributes.
+#[proc_macro_derive(Vertex, attributes(vertex))]
+pub fn derive_vertex(input: TokenStream) -> TokenStream {
+    vertex::derive(parse_macro_input!(input))
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+fn is_repr_c(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list),
+            _ => None,
+        })
+        .flat_map(|list| list.nested)
+        .any(|nested| matches!(nested, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("C")))
+}
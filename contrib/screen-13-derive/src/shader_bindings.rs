@@ -0,0 +1,218 @@
+use {
+    proc_macro2::TokenStream,
+    quote::quote,
+    syn::{
+        parse_quote, Attribute, Data, DeriveInput, Error, Ident, Index, Lit, Meta, NestedMeta,
+        Result,
+    },
+};
+
+enum Access {
+    Explicit(Ident),
+    Read,
+    Write,
+}
+
+struct FieldAttrs {
+    access: Access,
+    binding: u32,
+    index: Option<u32>,
+    set: u32,
+}
+
+impl FieldAttrs {
+    fn parse(attrs: &[Attribute], span: &impl quote::ToTokens) -> Result<Self> {
+        let mut access = None;
+        let mut binding = None;
+        let mut index = None;
+        let mut set = 0;
+
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("descriptor")) {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(Error::new_spanned(meta, "expected #[descriptor(..)]")),
+            };
+
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("read") => {
+                        access = Some(Access::Read)
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("write") => {
+                        access = Some(Access::Write)
+                    }
+                    NestedMeta::Meta(Meta::NameValue(name_value))
+                        if name_value.path.is_ident("access") =>
+                    {
+                        match &name_value.lit {
+                            Lit::Str(lit) => {
+                                access =
+                                    Some(Access::Explicit(Ident::new(&lit.value(), lit.span())))
+                            }
+                            lit => {
+                                return Err(Error::new_spanned(lit, "expected an access type name"))
+                            }
+                        }
+                    }
+                    NestedMeta::Meta(Meta::NameValue(name_value)) => {
+                        let value = match &name_value.lit {
+                            Lit::Int(lit) => lit.base10_parse()?,
+                            lit => return Err(Error::new_spanned(lit, "expected an integer")),
+                        };
+
+                        if name_value.path.is_ident("binding") {
+                            binding = Some(value);
+                        } else if name_value.path.is_ident("index") {
+                            index = Some(value);
+                        } else if name_value.path.is_ident("set") {
+                            set = value;
+                        } else {
+                            return Err(Error::new_spanned(
+                                name_value.path,
+                                "expected `access`, `binding`, `index`, or `set`",
+                            ));
+                        }
+                    }
+                    nested => {
+                        return Err(Error::new_spanned(
+                            nested,
+                            "expected `read`, `write`, `access = \"..\"`, `binding = ..`, \
+                            `index = ..`, or `set = ..`",
+                        ))
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            access: access.ok_or_else(|| {
+                Error::new_spanned(span, "expected `read`, `write`, or `access = \"..\"`")
+            })?,
+            binding: binding.ok_or_else(|| Error::new_spanned(span, "expected `binding = ..`"))?,
+            index,
+            set,
+        })
+    }
+}
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "ShaderBindings can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut binds = vec![];
+    let mut descriptors = vec![];
+    let mut bounds = vec![];
+    let mut kind_bounds = vec![];
+
+    for (idx, field) in fields.iter().enumerate() {
+        let (member, name) = match &field.ident {
+            Some(ident) => (quote!(#ident), ident.to_string()),
+            None => {
+                let idx = Index::from(idx);
+                (quote!(#idx), idx.index.to_string())
+            }
+        };
+        let attrs = FieldAttrs::parse(&field.attrs, field)?;
+        let set = attrs.set;
+        let binding = attrs.binding;
+        let descriptor = match attrs.index {
+            Some(index) => quote!(::screen_13::graph::Descriptor::ArrayBinding(
+                #set, #binding, #index
+            )),
+            None => quote!(::screen_13::graph::Descriptor::Binding(#set, #binding)),
+        };
+        let (access, is_write) = match attrs.access {
+            Access::Explicit(access) => (
+                quote!(::screen_13::driver::AccessType::#access),
+                quote!(::screen_13::driver::is_write_access(
+                    ::screen_13::driver::AccessType::#access
+                )),
+            ),
+            Access::Read => (
+                quote!(<__T as ::screen_13::graph::Access>::DEFAULT_READ),
+                quote!(false),
+            ),
+            Access::Write => (
+                quote!(<__T as ::screen_13::graph::Access>::DEFAULT_WRITE),
+                quote!(true),
+            ),
+        };
+        let ty = &field.ty;
+
+        binds.push(quote! {
+            let pass = ::screen_13::graph::DescriptorNode::access_descriptor(
+                self.#member,
+                pass,
+                #descriptor,
+                #access,
+            );
+        });
+        descriptors.push(quote!((
+            #name,
+            #descriptor,
+            <#ty as ::screen_13::graph::DescriptorNodeKind>::NODE_KIND,
+            #is_write,
+        )));
+        bounds.push(quote!(#ty: ::screen_13::graph::DescriptorNode<__P>));
+        kind_bounds.push(quote!(#ty: ::screen_13::graph::DescriptorNodeKind));
+    }
+
+    let ident = &input.ident;
+    let mut generics = input.generics.clone();
+    generics.params.push(parse_quote!(__P));
+    generics.make_where_clause().predicates.extend(
+        bounds
+            .into_iter()
+            .map(|bound| -> syn::WherePredicate { parse_quote!(#bound) }),
+    );
+    generics.make_where_clause().predicates.push(parse_quote!(
+        __P: ::screen_13::driver::archery::SharedPointerKind + Send + 'static
+    ));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let mut descriptors_generics = input.generics.clone();
+    descriptors_generics.make_where_clause().predicates.extend(
+        kind_bounds
+            .into_iter()
+            .map(|bound| -> syn::WherePredicate { parse_quote!(#bound) }),
+    );
+    let (descriptors_impl_generics, ty_generics, descriptors_where_clause) =
+        descriptors_generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::screen_13::graph::ShaderBindings<__P> for #ident #ty_generics
+            #where_clause
+        {
+            fn bind<'__a, __T>(
+                self,
+                pass: ::screen_13::graph::PipelinePassRef<'__a, __T, __P>,
+            ) -> ::screen_13::graph::PipelinePassRef<'__a, __T, __P>
+            where
+                __T: ::screen_13::graph::Access,
+            {
+                #(#binds)*
+
+                pass
+            }
+        }
+
+        impl #descriptors_impl_generics ::screen_13::graph::ShaderDescriptors for #ident #ty_generics
+            #descriptors_where_clause
+        {
+            fn descriptors() -> ::std::vec::Vec<(
+                &'static str,
+                ::screen_13::graph::Descriptor,
+                ::screen_13::graph::NodeKind,
+                bool,
+            )> {
+                ::std::vec![#(#descriptors),*]
+            }
+        }
+    })
+}
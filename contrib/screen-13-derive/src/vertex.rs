@@ -0,0 +1,183 @@
+use {
+    super::is_repr_c,
+    proc_macro2::{Span, TokenStream},
+    quote::quote,
+    syn::{
+        Attribute, Data, DeriveInput, Error, Fields, Ident, Index, Lit, Meta, NestedMeta, Result,
+    },
+};
+
+#[derive(Default)]
+struct FieldAttrs {
+    columns: Option<u32>,
+    format: Option<Ident>,
+    location: Option<u32>,
+    skip: bool,
+}
+
+impl FieldAttrs {
+    fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut res = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("vertex")) {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(Error::new_spanned(meta, "expected #[vertex(..)]")),
+            };
+
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => res.skip = true,
+                    NestedMeta::Meta(Meta::NameValue(name_value))
+                        if name_value.path.is_ident("columns") =>
+                    {
+                        match &name_value.lit {
+                            Lit::Int(lit) => res.columns = Some(lit.base10_parse()?),
+                            lit => {
+                                return Err(Error::new_spanned(lit, "expected a number of columns"))
+                            }
+                        }
+                    }
+                    NestedMeta::Meta(Meta::NameValue(name_value))
+                        if name_value.path.is_ident("format") =>
+                    {
+                        match &name_value.lit {
+                            Lit::Str(lit) => {
+                                res.format = Some(Ident::new(&lit.value(), lit.span()))
+                            }
+                            lit => return Err(Error::new_spanned(lit, "expected a format name")),
+                        }
+                    }
+                    NestedMeta::Meta(Meta::NameValue(name_value))
+                        if name_value.path.is_ident("location") =>
+                    {
+                        match &name_value.lit {
+                            Lit::Int(lit) => res.location = Some(lit.base10_parse()?),
+                            lit => return Err(Error::new_spanned(lit, "expected a location")),
+                        }
+                    }
+                    nested => {
+                        return Err(Error::new_spanned(
+                            nested,
+                            "expected `columns = ..`, `format = \"..\"`, `location = ..`, or \
+                            `skip`",
+                        ))
+                    }
+                }
+            }
+        }
+
+        Ok(res)
+    }
+}
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "Vertex can only be derived for structs",
+            ))
+        }
+    };
+
+    if !is_repr_c(&input.attrs) {
+        return Err(Error::new(
+            Span::call_site(),
+            "Vertex requires a #[repr(C)] struct",
+        ));
+    }
+
+    let mut attributes = vec![];
+
+    for (idx, field) in fields.iter().enumerate() {
+        let attrs = FieldAttrs::parse(&field.attrs)?;
+
+        if attrs.skip {
+            continue;
+        }
+
+        let location = attrs.location.map(|location| quote!(location = #location;));
+
+        let member = match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let idx = Index::from(idx);
+                quote!(#idx)
+            }
+        };
+        let ty = &field.ty;
+        let (columns, format) = match (attrs.columns, attrs.format) {
+            (columns, Some(format)) => {
+                let columns = columns.unwrap_or(1);
+
+                (
+                    quote!(#columns),
+                    quote!(::screen_13::driver::vk::Format::#format),
+                )
+            }
+            (Some(_), None) => {
+                return Err(Error::new_spanned(
+                    field,
+                    "`columns = ..` requires `format = \"..\"`",
+                ))
+            }
+            (None, None) => (
+                quote!(<#ty as ::screen_13::driver::VertexFormat>::COLUMNS),
+                quote!(<#ty as ::screen_13::driver::VertexFormat>::FORMAT),
+            ),
+        };
+
+        // Matrix columns each use the next location and are evenly spaced within the field
+        attributes.push(quote! {
+            #location
+
+            let columns: u32 = #columns;
+            let offset = unsafe {
+                ::std::ptr::addr_of!((*base).#member) as usize - base as usize
+            } as u32;
+            let stride = ::std::mem::size_of::<#ty>() as u32 / columns;
+
+            for column in 0..columns {
+                attributes.push(::screen_13::driver::vk::VertexInputAttributeDescription {
+                    location: location + column,
+                    binding,
+                    format: #format,
+                    offset: offset + column * stride,
+                });
+            }
+
+            location += columns;
+        });
+    }
+
+    if matches!(fields, Fields::Unit) || attributes.is_empty() {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "Vertex requires at least one field",
+        ));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::screen_13::driver::Vertex for #ident #ty_generics #where_clause {
+            #[allow(unused_assignments)]
+            fn vertex_attributes(
+                binding: u32,
+            ) -> ::std::vec::Vec<::screen_13::driver::vk::VertexInputAttributeDescription> {
+                // Field offsets are found without creating a reference to uninitialized data
+                let uninit = ::std::mem::MaybeUninit::<Self>::uninit();
+                let base = uninit.as_ptr();
+                let mut attributes = ::std::vec::Vec::new();
+                let mut location = 0u32;
+
+                #(#attributes)*
+
+                attributes
+            }
+        }
+    })
+}
@@ -46,7 +46,7 @@ where
         let pipeline = SharedPointer::new(
             GraphicPipeline::create(
                 device,
-                GraphicPipelineInfo::new().blend(BlendMode::Alpha),
+                GraphicPipelineInfo::new().blend(BlendMode::ALPHA),
                 [
                     Shader::new_vertex(
                         include_spirv!("res/shader/graphic/font.vert", vert).as_slice(),
@@ -54,14 +54,7 @@ where
                     Shader::new_fragment(
                         include_spirv!("res/shader/graphic/font.frag", frag).as_slice(),
                     )
-                    .specialization_info(SpecializationInfo::new(
-                        [vk::SpecializationMapEntry {
-                            constant_id: 0,
-                            offset: 0,
-                            size: 4,
-                        }],
-                        num_pages.to_ne_bytes(),
-                    )),
+                    .specialize("NUM_PAGES", num_pages),
                 ],
             )
             .context("Unable to create bitmap font pipeline")?,
@@ -0,0 +1,124 @@
+use {
+    super::GoldenError,
+    archery::SharedPointerKind,
+    log::warn,
+    png::{BitDepth, ColorType, Decoder, Encoder, Transformations},
+    screen_13::prelude_all::*,
+    std::{
+        fs::File,
+        io::{BufReader, BufWriter},
+        path::Path,
+    },
+};
+
+/// An 8-bit RGBA image stored in rows from top to bottom.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RgbaImage {
+    pub data: Vec<u8>,
+    pub height: u32,
+    pub width: u32,
+}
+
+impl RgbaImage {
+    /// Copies the offscreen image of a headless device into host memory.
+    ///
+    /// _NOTE:_ The offscreen image must use an 8-bit `RGBA` or `BGRA` format; `BGRA` images are
+    /// swizzled.
+    pub fn read_headless<P>(headless: &mut Headless<P>) -> Result<Self, GoldenError>
+    where
+        P: SharedPointerKind + Send + 'static,
+    {
+        let (fmt, width, height) = {
+            let info = &headless.image().info;
+
+            (info.fmt, info.width, info.height)
+        };
+        let is_bgra = match fmt {
+            vk::Format::B8G8R8A8_SRGB | vk::Format::B8G8R8A8_UNORM => true,
+            vk::Format::R8G8B8A8_SRGB | vk::Format::R8G8B8A8_UNORM => false,
+            _ => {
+                warn!("unsupported image format {fmt:?}");
+
+                return Err(GoldenError::Driver(DriverError::Unsupported));
+            }
+        };
+
+        let mut buffer = Some(Buffer::create(
+            &headless.device,
+            BufferInfo::new_mappable(
+                width as vk::DeviceSize * height as vk::DeviceSize * 4,
+                vk::BufferUsageFlags::TRANSFER_DST,
+            ),
+        )?);
+        let mut buffer_binding = None;
+
+        headless.run_frames(1, |frame| {
+            let buffer = frame.render_graph.bind_node(buffer.take().unwrap());
+            frame.render_graph.copy_image_to_buffer(frame.image, buffer);
+            buffer_binding = Some(frame.render_graph.unbind_node(buffer));
+        })?;
+
+        let mut data = Buffer::mapped_slice(buffer_binding.as_ref().unwrap().get()).to_vec();
+
+        if is_bgra {
+            for pixel in data.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+
+        Ok(Self {
+            data,
+            height,
+            width,
+        })
+    }
+
+    /// Reads a PNG file, converting it to 8-bit RGBA.
+    pub fn read_png(path: impl AsRef<Path>) -> Result<Self, GoldenError> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|err| GoldenError::Io(path.to_path_buf(), err))?;
+        let mut decoder = Decoder::new(BufReader::new(file));
+        decoder.set_transformations(Transformations::normalize_to_color8());
+
+        let mut reader = decoder
+            .read_info()
+            .map_err(|err| GoldenError::Decode(path.to_path_buf(), err))?;
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader
+            .next_frame(&mut buf)
+            .map_err(|err| GoldenError::Decode(path.to_path_buf(), err))?;
+        let buf = &buf[..info.buffer_size()];
+        let data = match info.color_type {
+            ColorType::Grayscale => buf.iter().flat_map(|&l| [l, l, l, 0xff]).collect(),
+            ColorType::GrayscaleAlpha => buf
+                .chunks_exact(2)
+                .flat_map(|la| [la[0], la[0], la[0], la[1]])
+                .collect(),
+            ColorType::Rgb => buf
+                .chunks_exact(3)
+                .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 0xff])
+                .collect(),
+            ColorType::Rgba => buf.to_vec(),
+            ColorType::Indexed => unreachable!("indexed images are expanded"),
+        };
+
+        Ok(Self {
+            data,
+            height: info.height,
+            width: info.width,
+        })
+    }
+
+    /// Writes an 8-bit RGBA PNG file.
+    pub fn write_png(&self, path: impl AsRef<Path>) -> Result<(), GoldenError> {
+        let path = path.as_ref();
+        let file = File::create(path).map_err(|err| GoldenError::Io(path.to_path_buf(), err))?;
+        let mut encoder = Encoder::new(BufWriter::new(file), self.width, self.height);
+        encoder.set_color(ColorType::Rgba);
+        encoder.set_depth(BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&self.data))
+            .map_err(|err| GoldenError::Encode(path.to_path_buf(), err))
+    }
+}
@@ -0,0 +1,236 @@
+//! Golden-image regression testing: frames are rendered using a [`Headless`] device and the final
+//! image is compared against a stored reference PNG file.
+//!
+//! Set the `SCREEN_13_BLESS` environment variable to write the rendered images as the new
+//! references instead of comparing them.
+
+mod image;
+mod report;
+
+pub use self::{image::RgbaImage, report::GoldenReport};
+
+use {
+    archery::SharedPointerKind,
+    derive_builder::Builder,
+    log::{info, warn},
+    screen_13::prelude_all::*,
+    std::{
+        env::var_os,
+        error::Error,
+        fmt::{Display, Formatter},
+        fs::create_dir_all,
+        io,
+        path::PathBuf,
+    },
+};
+
+/// The environment variable which, when set to anything other than `0`, causes rendered images to
+/// be written as the new references.
+pub const BLESS_ENV_VAR: &str = "SCREEN_13_BLESS";
+
+/// Compares the offscreen image of a headless device against the reference image with the given
+/// name, using the default settings of [`GoldenTest`], and panics if it does not match.
+pub fn assert_golden<P>(headless: &mut Headless<P>, name: impl Into<String>)
+where
+    P: SharedPointerKind + Send + 'static,
+{
+    let test = GoldenTest::new(name).build();
+
+    if let Err(err) = test.check(headless) {
+        panic!("golden image `{}`: {err}", test.name);
+    }
+}
+
+fn is_bless() -> bool {
+    var_os(BLESS_ENV_VAR)
+        .map(|value| !value.is_empty() && value != "0")
+        .unwrap_or_default()
+}
+
+/// Describes an error which occurs while checking a golden image.
+#[derive(Debug)]
+pub enum GoldenError {
+    /// A PNG file could not be read.
+    Decode(PathBuf, png::DecodingError),
+    /// The image could not be rendered or read back.
+    Driver(DriverError),
+    /// A PNG file could not be written.
+    Encode(PathBuf, png::EncodingError),
+    /// A file could not be opened or created.
+    Io(PathBuf, io::Error),
+    /// The rendered image does not match the reference image; the rendered and difference images
+    /// have been written next to the reference image.
+    Mismatch(GoldenReport),
+    /// The reference image does not exist; see [`BLESS_ENV_VAR`].
+    MissingReference(PathBuf),
+    /// The rendered image is not the same size as the reference image.
+    SizeMismatch {
+        actual: (u32, u32),
+        expected: (u32, u32),
+    },
+}
+
+impl Display for GoldenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Decode(path, err) => write!(f, "{}: {err}", path.display()),
+            Self::Driver(err) => write!(f, "{err}"),
+            Self::Encode(path, err) => write!(f, "{}: {err}", path.display()),
+            Self::Io(path, err) => write!(f, "{}: {err}", path.display()),
+            Self::Mismatch(report) => write!(f, "{report}"),
+            Self::MissingReference(path) => write!(
+                f,
+                "{}: reference image not found (set {BLESS_ENV_VAR}=1 to create it)",
+                path.display()
+            ),
+            Self::SizeMismatch { actual, expected } => write!(
+                f,
+                "image is {}x{} but the reference image is {}x{}",
+                actual.0, actual.1, expected.0, expected.1
+            ),
+        }
+    }
+}
+
+impl Error for GoldenError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Decode(_, err) => Some(err),
+            Self::Driver(err) => Some(err),
+            Self::Encode(_, err) => Some(err),
+            Self::Io(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<DriverError> for GoldenError {
+    fn from(err: DriverError) -> Self {
+        Self::Driver(err)
+    }
+}
+
+/// Describes how a rendered image is compared against a reference image.
+#[derive(Builder, Clone, Debug)]
+#[builder(build_fn(private, name = "fallible_build"), pattern = "owned")]
+pub struct GoldenTest {
+    /// The smallest peak signal-to-noise ratio, in decibels, which passes.
+    #[builder(default, setter(strip_option))]
+    pub min_psnr: Option<f64>,
+
+    /// The smallest mean structural similarity which passes.
+    #[builder(default, setter(strip_option))]
+    pub min_ssim: Option<f64>,
+
+    /// The name of the reference image, without the `.png` extension.
+    #[builder(setter(into))]
+    pub name: String,
+
+    /// The directory of reference images; `tests/golden` of the package being tested by default.
+    #[builder(default = "default_reference_dir()", setter(into))]
+    pub reference_dir: PathBuf,
+
+    /// The largest difference of any channel of a pixel which passes; zero (pixel-exact) by
+    /// default.
+    #[builder(default)]
+    pub tolerance: u8,
+}
+
+fn default_reference_dir() -> PathBuf {
+    var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join("tests")
+        .join("golden")
+}
+
+impl GoldenTest {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(name: impl Into<String>) -> GoldenTestBuilder {
+        GoldenTestBuilder::default().name(name)
+    }
+
+    /// Reads back the offscreen image of a headless device and compares it against the reference
+    /// image.
+    ///
+    /// If [`BLESS_ENV_VAR`] is set the image is written as the reference image instead.
+    pub fn check<P>(&self, headless: &mut Headless<P>) -> Result<GoldenReport, GoldenError>
+    where
+        P: SharedPointerKind + Send + 'static,
+    {
+        let image = RgbaImage::read_headless(headless)?;
+
+        self.check_image(&image)
+    }
+
+    /// Compares an image against the reference image.
+    ///
+    /// If [`BLESS_ENV_VAR`] is set the image is written as the reference image instead.
+    pub fn check_image(&self, image: &RgbaImage) -> Result<GoldenReport, GoldenError> {
+        let reference_path = self.path("png");
+
+        if is_bless() {
+            create_dir_all(&self.reference_dir)
+                .map_err(|err| GoldenError::Io(self.reference_dir.clone(), err))?;
+            image.write_png(&reference_path)?;
+
+            info!("blessed {}", reference_path.display());
+
+            return Ok(GoldenReport::blessed(image));
+        }
+
+        if !reference_path.is_file() {
+            return Err(GoldenError::MissingReference(reference_path));
+        }
+
+        let reference = RgbaImage::read_png(&reference_path)?;
+
+        if (image.width, image.height) != (reference.width, reference.height) {
+            return Err(GoldenError::SizeMismatch {
+                actual: (image.width, image.height),
+                expected: (reference.width, reference.height),
+            });
+        }
+
+        let (report, diff) = GoldenReport::compare(image, &reference, self.tolerance);
+        let is_match = report.mismatched_pixels == 0
+            && self
+                .min_psnr
+                .map(|min_psnr| report.psnr >= min_psnr)
+                .unwrap_or(true)
+            && self
+                .min_ssim
+                .map(|min_ssim| report.ssim >= min_ssim)
+                .unwrap_or(true);
+
+        if is_match {
+            return Ok(report);
+        }
+
+        warn!("{}: {report}", reference_path.display());
+
+        image.write_png(self.path("actual.png"))?;
+        diff.write_png(self.path("diff.png"))?;
+
+        Err(GoldenError::Mismatch(report))
+    }
+
+    fn path(&self, extension: &str) -> PathBuf {
+        self.reference_dir
+            .join(format!("{}.{extension}", self.name))
+    }
+}
+
+// HACK: https://github.com/colin-kiegel/rust-derive-builder/issues/56
+impl GoldenTestBuilder {
+    pub fn build(self) -> GoldenTest {
+        self.fallible_build()
+            .expect("All required fields set at initialization")
+    }
+}
+
+impl From<GoldenTestBuilder> for GoldenTest {
+    fn from(test: GoldenTestBuilder) -> Self {
+        test.build()
+    }
+}
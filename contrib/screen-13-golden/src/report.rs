@@ -0,0 +1,279 @@
+use {
+    super::RgbaImage,
+    std::fmt::{Display, Formatter},
+};
+
+const SSIM_C1: f64 = (0.01 * 255.0) * (0.01 * 255.0);
+const SSIM_C2: f64 = (0.03 * 255.0) * (0.03 * 255.0);
+const SSIM_STRIDE: usize = 4;
+const SSIM_WINDOW: usize = 8;
+
+fn luma(pixel: &[u8]) -> f64 {
+    0.299 * pixel[0] as f64 + 0.587 * pixel[1] as f64 + 0.114 * pixel[2] as f64
+}
+
+/// Returns the peak signal-to-noise ratio, in decibels, of all channels of two images of the same
+/// size; identical images are infinite.
+fn psnr(actual: &RgbaImage, reference: &RgbaImage) -> f64 {
+    let squared_error = actual
+        .data
+        .iter()
+        .zip(reference.data.iter())
+        .map(|(&a, &b)| {
+            let diff = a as f64 - b as f64;
+            diff * diff
+        })
+        .sum::<f64>();
+
+    if squared_error == 0.0 {
+        return f64::INFINITY;
+    }
+
+    let mse = squared_error / actual.data.len() as f64;
+
+    10.0 * (255.0 * 255.0 / mse).log10()
+}
+
+/// Returns the mean structural similarity of the luma of two images of the same size, using
+/// overlapping square windows; identical (or empty) images are `1.0`.
+fn ssim(actual: &RgbaImage, reference: &RgbaImage) -> f64 {
+    let width = actual.width as usize;
+    let height = actual.height as usize;
+
+    if width == 0 || height == 0 {
+        return 1.0;
+    }
+
+    let actual = actual.data.chunks_exact(4).map(luma).collect::<Vec<_>>();
+    let reference = reference.data.chunks_exact(4).map(luma).collect::<Vec<_>>();
+    let window_width = SSIM_WINDOW.min(width);
+    let window_height = SSIM_WINDOW.min(height);
+    let window_len = (window_width * window_height) as f64;
+
+    let mut sum = 0.0;
+    let mut count = 0;
+
+    for y in (0..=height - window_height).step_by(SSIM_STRIDE) {
+        for x in (0..=width - window_width).step_by(SSIM_STRIDE) {
+            let window = (y..y + window_height)
+                .flat_map(|y| (x..x + window_width).map(move |x| y * width + x));
+            let (mut mean_a, mut mean_b) = (0.0, 0.0);
+
+            for idx in window.clone() {
+                mean_a += actual[idx];
+                mean_b += reference[idx];
+            }
+
+            mean_a /= window_len;
+            mean_b /= window_len;
+
+            let (mut var_a, mut var_b, mut covar) = (0.0, 0.0, 0.0);
+
+            for idx in window {
+                let a = actual[idx] - mean_a;
+                let b = reference[idx] - mean_b;
+                var_a += a * a;
+                var_b += b * b;
+                covar += a * b;
+            }
+
+            var_a /= window_len;
+            var_b /= window_len;
+            covar /= window_len;
+
+            sum += ((2.0 * mean_a * mean_b + SSIM_C1) * (2.0 * covar + SSIM_C2))
+                / ((mean_a * mean_a + mean_b * mean_b + SSIM_C1) * (var_a + var_b + SSIM_C2));
+            count += 1;
+        }
+    }
+
+    sum / count as f64
+}
+
+/// The result of comparing a rendered image against a reference image.
+#[derive(Clone, Debug)]
+pub struct GoldenReport {
+    /// `true` if the reference image was written instead of compared.
+    pub blessed: bool,
+
+    /// The largest difference of each of the red, green, blue, and alpha channels.
+    pub max_channel_diff: [u8; 4],
+
+    /// The number of pixels with any channel difference larger than the tolerance.
+    pub mismatched_pixels: usize,
+
+    /// Peak signal-to-noise ratio of all channels, in decibels; infinite for identical images.
+    pub psnr: f64,
+
+    /// Mean structural similarity of the luma of the images; `1.0` for identical images.
+    pub ssim: f64,
+
+    /// The total number of pixels compared.
+    pub total_pixels: usize,
+}
+
+impl GoldenReport {
+    pub(super) fn blessed(image: &RgbaImage) -> Self {
+        Self {
+            blessed: true,
+            max_channel_diff: [0; 4],
+            mismatched_pixels: 0,
+            psnr: f64::INFINITY,
+            ssim: 1.0,
+            total_pixels: image.data.len() / 4,
+        }
+    }
+
+    /// Compares two images of the same size, returning the report and an image which shows
+    /// mismatched pixels in red over a dimmed copy of the reference image.
+    pub(super) fn compare(
+        actual: &RgbaImage,
+        reference: &RgbaImage,
+        tolerance: u8,
+    ) -> (Self, RgbaImage) {
+        debug_assert_eq!(actual.width, reference.width);
+        debug_assert_eq!(actual.height, reference.height);
+
+        let mut max_channel_diff = [0u8; 4];
+        let mut mismatched_pixels = 0;
+        let mut diff = Vec::with_capacity(reference.data.len());
+
+        for (a, b) in actual
+            .data
+            .chunks_exact(4)
+            .zip(reference.data.chunks_exact(4))
+        {
+            let mut is_mismatched = false;
+
+            for channel in 0..4 {
+                let channel_diff = a[channel].abs_diff(b[channel]);
+                max_channel_diff[channel] = max_channel_diff[channel].max(channel_diff);
+                is_mismatched |= channel_diff > tolerance;
+            }
+
+            if is_mismatched {
+                mismatched_pixels += 1;
+                diff.extend_from_slice(&[0xff, 0x00, 0x00, 0xff]);
+            } else {
+                let l = (luma(b) / 4.0) as u8;
+                diff.extend_from_slice(&[l, l, l, 0xff]);
+            }
+        }
+
+        (
+            Self {
+                blessed: false,
+                max_channel_diff,
+                mismatched_pixels,
+                psnr: psnr(actual, reference),
+                ssim: ssim(actual, reference),
+                total_pixels: reference.data.len() / 4,
+            },
+            RgbaImage {
+                data: diff,
+                height: reference.height,
+                width: reference.width,
+            },
+        )
+    }
+}
+
+impl Display for GoldenReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.blessed {
+            return f.write_str("reference image blessed");
+        }
+
+        let [r, g, b, a] = self.max_channel_diff;
+
+        write!(
+            f,
+            "{} of {} pixels differ (max channel difference r={r} g={g} b={b} a={a}), PSNR {:.2} dB, SSIM {:.4}",
+            self.mismatched_pixels, self.total_pixels, self.psnr, self.ssim
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gray_image(width: u32, height: u32) -> RgbaImage {
+        RgbaImage {
+            data: (0..width * height)
+                .flat_map(|idx| {
+                    let l = (idx * 7 % 256) as u8;
+                    [l, l, l, 0xff]
+                })
+                .collect(),
+            height,
+            width,
+        }
+    }
+
+    #[test]
+    fn empty_images() {
+        let image = gray_image(0, 0);
+        let (report, diff) = GoldenReport::compare(&image, &image, 0);
+
+        assert_eq!(report.mismatched_pixels, 0);
+        assert_eq!(report.total_pixels, 0);
+        assert!(report.psnr.is_infinite());
+        assert_eq!(report.ssim, 1.0);
+        assert!(diff.data.is_empty());
+    }
+
+    #[test]
+    fn identical_images() {
+        let image = gray_image(16, 12);
+        let (report, _) = GoldenReport::compare(&image, &image, 0);
+
+        assert_eq!(report.max_channel_diff, [0; 4]);
+        assert_eq!(report.mismatched_pixels, 0);
+        assert_eq!(report.total_pixels, 16 * 12);
+        assert_eq!(report.psnr, f64::INFINITY);
+        assert_eq!(report.ssim, 1.0);
+    }
+
+    #[test]
+    fn one_pixel_difference() {
+        let reference = gray_image(16, 12);
+        let mut actual = reference.clone();
+        actual.data[4 * 20] = actual.data[4 * 20].wrapping_add(3);
+
+        let (report, diff) = GoldenReport::compare(&actual, &reference, 2);
+
+        assert_eq!(report.max_channel_diff, [3, 0, 0, 0]);
+        assert_eq!(report.mismatched_pixels, 1);
+        assert!(report.psnr.is_finite());
+        assert!(report.ssim < 1.0);
+        assert_eq!(&diff.data[4 * 20..4 * 21], &[0xff, 0x00, 0x00, 0xff]);
+
+        let (report, _) = GoldenReport::compare(&actual, &reference, 3);
+
+        assert_eq!(report.mismatched_pixels, 0);
+    }
+
+    #[test]
+    fn psnr_of_uniform_error() {
+        let reference = gray_image(8, 8);
+        let mut actual = reference.clone();
+
+        for value in &mut actual.data {
+            *value ^= 1;
+        }
+
+        // Every channel differs by exactly one, so MSE is one
+        let expected = 10.0 * (255.0f64 * 255.0).log10();
+
+        assert!((psnr(&actual, &reference) - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn small_images() {
+        // Images smaller than the SSIM window use a single window the size of the image
+        let image = gray_image(3, 2);
+
+        assert_eq!(ssim(&image, &image), 1.0);
+    }
+}
@@ -0,0 +1,14 @@
+use screen_13::prelude_arc::*;
+
+pub const HEIGHT: u32 = 200;
+pub const WIDTH: u32 = 320;
+
+/// Returns a headless device.
+///
+/// _NOTE:_ Panics if this machine has no Vulkan device; golden tests are ignored by default and
+/// must be run using `cargo test -- --ignored` on a machine which has one.
+pub fn headless() -> Headless {
+    Headless::new(WIDTH, HEIGHT)
+        .build()
+        .expect("golden tests require a Vulkan device")
+}
@@ -0,0 +1,135 @@
+//! Golden tests of the `screen-13-fx` transitions shown by `examples/transitions.rs`, using two
+//! generated images instead of photographs.
+
+mod common;
+
+use {screen_13::prelude_arc::*, screen_13_fx::prelude_arc::*, screen_13_golden::assert_golden};
+
+/// A horizontal red-to-blue gradient.
+fn gradient_pixels() -> Vec<u8> {
+    (0..common::HEIGHT)
+        .flat_map(|_| {
+            (0..common::WIDTH).flat_map(|x| {
+                let t = (x * 255 / (common::WIDTH - 1)) as u8;
+
+                [0xff - t, 0x00, t, 0xff]
+            })
+        })
+        .collect()
+}
+
+/// A green and white checkerboard of 20 pixel squares.
+fn checkerboard_pixels() -> Vec<u8> {
+    (0..common::HEIGHT)
+        .flat_map(|y| {
+            (0..common::WIDTH).flat_map(move |x| {
+                if (x / 20 + y / 20) % 2 == 0 {
+                    [0x00, 0xc0, 0x00, 0xff]
+                } else {
+                    [0xff, 0xff, 0xff, 0xff]
+                }
+            })
+        })
+        .collect()
+}
+
+fn check_transition(name: &str, transition: Transition) {
+    let mut headless = common::headless();
+
+    let mut image_loader = ImageLoader::new(&headless.device).unwrap();
+    let mut transition_pipeline = TransitionPipeline::new(&headless.device);
+    let mut a_image = Some(
+        image_loader
+            .decode_linear(
+                &gradient_pixels(),
+                ImageFormat::R8G8B8A8,
+                common::WIDTH,
+                common::HEIGHT,
+            )
+            .unwrap(),
+    );
+    let mut b_image = Some(
+        image_loader
+            .decode_linear(
+                &checkerboard_pixels(),
+                ImageFormat::R8G8B8A8,
+                common::WIDTH,
+                common::HEIGHT,
+            )
+            .unwrap(),
+    );
+
+    headless
+        .run_frames(1, |frame| {
+            let a_node = frame.render_graph.bind_node(a_image.take().unwrap());
+            let b_node = frame.render_graph.bind_node(b_image.take().unwrap());
+            let blend_image =
+                transition_pipeline.apply(frame.render_graph, a_node, b_node, &transition, 0.5);
+
+            frame.render_graph.copy_image(blend_image, frame.image);
+
+            a_image = Some(frame.render_graph.unbind_node(a_node));
+            b_image = Some(frame.render_graph.unbind_node(b_node));
+        })
+        .unwrap();
+
+    assert_golden(&mut headless, format!("fx_transition_{name}"));
+}
+
+#[test]
+#[ignore = "requires a Vulkan device"]
+fn angular() {
+    check_transition(
+        "angular",
+        Transition::Angular {
+            starting_angle: 90.0,
+        },
+    );
+}
+
+#[test]
+#[ignore = "requires a Vulkan device"]
+fn burn() {
+    check_transition(
+        "burn",
+        Transition::Burn {
+            color: [0.9, 0.4, 0.2],
+        },
+    );
+}
+
+#[test]
+#[ignore = "requires a Vulkan device"]
+fn circle_open() {
+    check_transition(
+        "circle_open",
+        Transition::CircleOpen {
+            smoothness: 0.3,
+            opening: true,
+        },
+    );
+}
+
+#[test]
+#[ignore = "requires a Vulkan device"]
+fn fade() {
+    check_transition("fade", Transition::Fade);
+}
+
+#[test]
+#[ignore = "requires a Vulkan device"]
+fn heart() {
+    check_transition("heart", Transition::Heart);
+}
+
+#[test]
+#[ignore = "requires a Vulkan device"]
+fn swirl() {
+    check_transition("swirl", Transition::Swirl);
+}
+
+#[test]
+#[ignore = "requires a Vulkan device"]
+fn wipe_left() {
+    check_transition("wipe_left", Transition::WipeLeft);
+}
@@ -0,0 +1,246 @@
+//! Golden test of `examples/multipass.rs`.
+
+mod common;
+
+use {
+    bytemuck::cast_slice,
+    glam::{Mat4, Vec4},
+    inline_spirv::inline_spirv,
+    screen_13::prelude_arc::*,
+    screen_13_golden::assert_golden,
+};
+
+#[test]
+#[ignore = "requires a Vulkan device"]
+fn multipass() {
+    let mut headless = common::headless();
+
+    let fill_quad_linear_gradient = create_fill_quad_linear_gradient_pipeline(&headless.device);
+    let draw_funky_shape_deferred = create_draw_funky_shape_deferred_pipeline(&headless.device);
+    let mut cache = HashPool::new(&headless.device);
+
+    let (indices, vertices) = funky_shape_triangle_mesh_buffers();
+    let index_count = indices.len() as u32;
+    let indices = cast_slice(&indices);
+    let vertices = cast_slice(&vertices);
+
+    let image_info = ImageInfo::new_2d(
+        vk::Format::R8G8B8A8_UNORM,
+        common::WIDTH,
+        common::HEIGHT,
+        vk::ImageUsageFlags::SAMPLED
+            | vk::ImageUsageFlags::COLOR_ATTACHMENT
+            | vk::ImageUsageFlags::TRANSFER_SRC,
+    )
+    .build();
+
+    let red = [0xffu8, 0x00, 0x00, 0xff];
+    let green = [0x00u8, 0xff, 0x00, 0xff];
+    let blue = [0x00u8, 0x00, 0xff, 0xff];
+    let magenta = [0xffu8, 0x00, 0xff, 0xff];
+    let white = [0xffu8, 0xff, 0xff, 0xff];
+
+    headless
+        .run_frames(1, |frame| {
+            let graph = frame.render_graph;
+
+            let index_buf = graph.bind_node({
+                let mut buf = cache
+                    .lease(BufferInfo::new_mappable(
+                        indices.len() as _,
+                        vk::BufferUsageFlags::INDEX_BUFFER,
+                    ))
+                    .unwrap();
+                Buffer::copy_from_slice(buf.get_mut().unwrap(), 0, indices);
+                buf
+            });
+            let vertex_buf = graph.bind_node({
+                let mut buf = cache
+                    .lease(BufferInfo::new_mappable(
+                        vertices.len() as _,
+                        vk::BufferUsageFlags::VERTEX_BUFFER,
+                    ))
+                    .unwrap();
+                Buffer::copy_from_slice(buf.get_mut().unwrap(), 0, vertices);
+                buf
+            });
+            let image1 = graph.bind_node(cache.lease(image_info).unwrap());
+            let image2 = graph.bind_node(cache.lease(image_info).unwrap());
+            let image3 = graph.bind_node(cache.lease(image_info).unwrap());
+
+            graph
+                .begin_pass("gradients")
+                .bind_pipeline(&fill_quad_linear_gradient)
+                .clear_color_value(0, white)
+                .store_color(0, image1)
+                .record_subpass(move |subpass| {
+                    subpass.push_constants_offset(0, &red);
+                    subpass.push_constants_offset(4, &blue);
+                    subpass.draw(6, 1, 0, 0);
+                })
+                .store_color(0, image2)
+                .record_subpass(move |subpass| {
+                    subpass.push_constants_offset(0, &magenta);
+                    subpass.push_constants_offset(4, &green);
+                    subpass.draw(6, 1, 0, 0);
+                });
+
+            graph
+                .begin_pass("funky shape")
+                .bind_pipeline(&draw_funky_shape_deferred)
+                .access_node(index_buf, AccessType::IndexBuffer)
+                .access_node(vertex_buf, AccessType::VertexBuffer)
+                .clear_color(0)
+                .read_descriptor((0, [0]), image1)
+                .read_descriptor((0, [1]), image2)
+                .store_color(0, image3)
+                .record_subpass(move |subpass| {
+                    subpass
+                        .push_constants_offset(0, cast_slice(&Mat4::IDENTITY.to_cols_array()))
+                        .push_constants_offset(64, cast_slice(&Vec4::ONE.to_array()))
+                        .bind_index_buffer(index_buf, vk::IndexType::UINT32)
+                        .bind_vertex_buffer(vertex_buf)
+                        .draw(index_count, 1, 0, 0);
+                });
+
+            graph.copy_image(image3, frame.image);
+        })
+        .unwrap();
+
+    assert_golden(&mut headless, "multipass");
+}
+
+fn create_fill_quad_linear_gradient_pipeline(device: &Shared<Device>) -> Shared<GraphicPipeline> {
+    let vertex_shader = Shader::new_vertex(
+        inline_spirv!(
+            r#"
+            #version 460 core
+
+            const vec2 POSITION[6] = vec2[6](
+                vec2(-1, -1), vec2(-1,  1), vec2( 1, -1),
+                vec2( 1,  1), vec2( 1, -1), vec2(-1,  1)
+            );
+
+            layout(location = 0) out float vk_Blend;
+
+            void main() {
+                gl_Position = vec4(POSITION[gl_VertexIndex], 0, 1);
+                vk_Blend = gl_Position.x * -0.5 + 0.5;
+            }
+            "#,
+            vert
+        )
+        .as_slice(),
+    );
+    let fragment_shader = Shader::new_fragment(
+        inline_spirv!(
+            r#"
+            #version 460 core
+
+            layout(push_constant) uniform PushConstants {
+                layout(offset = 0) vec4 start_color;
+                layout(offset = 16) vec4 end_color;
+            } push_constants;
+
+            layout(location = 0) in float blend;
+
+            layout(location = 0) out vec4 vk_Color;
+
+            void main() {
+                vk_Color = mix(push_constants.start_color, push_constants.end_color, blend);
+            }
+            "#,
+            frag
+        )
+        .as_slice(),
+    );
+
+    Shared::new(
+        GraphicPipeline::create(
+            device,
+            GraphicPipelineInfo::new().blend(BlendMode::ALPHA),
+            [vertex_shader, fragment_shader],
+        )
+        .unwrap(),
+    )
+}
+
+fn create_draw_funky_shape_deferred_pipeline(device: &Shared<Device>) -> Shared<GraphicPipeline> {
+    let vertex_shader = Shader::new_vertex(
+        inline_spirv!(
+            r#"
+            #version 460 core
+
+            layout(push_constant) uniform PushConstants {
+                layout(offset = 0) mat4 transform;
+            } push_constants;
+
+            layout(location = 0) in vec3 position;
+            layout(location = 1) in vec3 normal;
+
+            layout(location = 0) out vec3 vk_Normal;
+
+            void main() {
+                gl_Position = push_constants.transform * vec4(position, 1);
+                vk_Normal = normal;
+            }
+            "#,
+            vert
+        )
+        .as_slice(),
+    );
+    let fragment_shader = Shader::new_fragment(
+        inline_spirv!(
+            r#"
+            #version 460 core
+
+            layout(push_constant) uniform PushConstants {
+                layout(offset = 0) mat4 transform;
+                layout(offset = 64) vec4 coolness_factor;
+            } push_constants;
+
+            layout(location = 0) in vec3 normal;
+
+            layout(location = 0) out vec4 vk_Color;
+
+            void main() {
+                vk_Color = push_constants.coolness_factor * vec4(normal, 1);
+            }
+            "#,
+            frag
+        )
+        .as_slice(),
+    );
+
+    Shared::new(
+        GraphicPipeline::create(
+            device,
+            GraphicPipelineInfo::new()
+                .cull_mode(vk::CullModeFlags::NONE)
+                .two_sided(true),
+            [vertex_shader, fragment_shader],
+        )
+        .unwrap(),
+    )
+}
+
+fn funky_shape_triangle_mesh_buffers() -> (Vec<u32>, Vec<[f32; 6]>) {
+    let (indices, positions, normals) = polyhedron_ops::Polyhedron::dodecahedron()
+        .chamfer(None, true)
+        .propeller(None, true)
+        .ambo(None, true)
+        .gyro(None, None, true)
+        .finalize()
+        .to_triangle_mesh_buffers();
+    let vertices = positions
+        .into_iter()
+        .zip(normals.into_iter())
+        .map(|(position, normal)| {
+            [
+                position.x, position.y, position.z, normal.x, normal.y, normal.z,
+            ]
+        })
+        .collect();
+
+    (indices, vertices)
+}
@@ -0,0 +1,116 @@
+//! Golden test of `examples/triangle.rs`.
+
+mod common;
+
+use {
+    bytemuck::cast_slice, inline_spirv::inline_spirv, screen_13::prelude_arc::*,
+    screen_13_golden::assert_golden,
+};
+
+#[test]
+#[ignore = "requires a Vulkan device"]
+fn triangle() {
+    let mut headless = common::headless();
+
+    let triangle_pipeline = Shared::new(
+        GraphicPipeline::create(
+            &headless.device,
+            GraphicPipelineInfo::default(),
+            [
+                Shader::new_vertex(
+                    inline_spirv!(
+                        r#"
+                        #version 460 core
+
+                        layout(location = 0) in vec3 position;
+                        layout(location = 1) in vec3 color;
+
+                        layout(location = 0) out vec3 vk_Color;
+
+                        void main() {
+                            gl_Position = vec4(position, 1);
+                            vk_Color = color;
+                        }
+                        "#,
+                        vert
+                    )
+                    .as_slice(),
+                ),
+                Shader::new_fragment(
+                    inline_spirv!(
+                        r#"
+                        #version 460 core
+
+                        layout(location = 0) in vec3 color;
+
+                        layout(location = 0) out vec4 vk_Color;
+
+                        void main() {
+                            vk_Color = vec4(color, 1);
+                        }
+                        "#,
+                        frag
+                    )
+                    .as_slice(),
+                ),
+            ],
+        )
+        .unwrap(),
+    );
+
+    let mut index_buf = Some(BufferBinding::new({
+        let mut buf = Buffer::create(
+            &headless.device,
+            BufferInfo::new_mappable(6, vk::BufferUsageFlags::INDEX_BUFFER),
+        )
+        .unwrap();
+        Buffer::copy_from_slice(&mut buf, 0, cast_slice(&[0u16, 1, 2]));
+        buf
+    }));
+    let mut vertex_buf = Some(BufferBinding::new({
+        let mut buf = Buffer::create(
+            &headless.device,
+            BufferInfo::new_mappable(72, vk::BufferUsageFlags::VERTEX_BUFFER),
+        )
+        .unwrap();
+        Buffer::copy_from_slice(
+            &mut buf,
+            0,
+            cast_slice(&[
+                1.0f32, 1.0, 0.0, // v1
+                1.0, 0.0, 0.0, // red
+                0.0, -1.0, 0.0, // v2
+                0.0, 1.0, 0.0, // green
+                -1.0, 1.0, 0.0, // v3
+                0.0, 0.0, 1.0, // blue
+            ]),
+        );
+        buf
+    }));
+
+    headless
+        .run_frames(1, |frame| {
+            let index_node = frame.render_graph.bind_node(index_buf.take().unwrap());
+            let vertex_node = frame.render_graph.bind_node(vertex_buf.take().unwrap());
+
+            frame
+                .render_graph
+                .begin_pass("Triangle Example")
+                .bind_pipeline(&triangle_pipeline)
+                .access_node(index_node, AccessType::IndexBuffer)
+                .access_node(vertex_node, AccessType::VertexBuffer)
+                .clear_color(0)
+                .store_color(0, frame.image)
+                .record_subpass(move |subpass| {
+                    subpass.bind_index_buffer(index_node, vk::IndexType::UINT16);
+                    subpass.bind_vertex_buffer(vertex_node);
+                    subpass.draw_indexed(3, 1, 0, 0, 0);
+                });
+
+            index_buf = Some(frame.render_graph.unbind_node(index_node));
+            vertex_buf = Some(frame.render_graph.unbind_node(vertex_node));
+        })
+        .unwrap();
+
+    assert_golden(&mut headless, "triangle");
+}
@@ -0,0 +1,32 @@
+pub mod prelude_arc {
+    pub use super::*;
+
+    use archery::ArcK as P;
+
+    pub type HotComputePipeline = super::HotComputePipeline<P>;
+    pub type HotGraphicPipeline = super::HotGraphicPipeline<P>;
+    pub type HotPipelineRegistry = super::HotPipelineRegistry<P>;
+    pub type HotRayTracePipeline = super::HotRayTracePipeline<P>;
+}
+
+pub mod prelude_rc {
+    pub use super::*;
+
+    use archery::RcK as P;
+
+    pub type HotComputePipeline = super::HotComputePipeline<P>;
+    pub type HotGraphicPipeline = super::HotGraphicPipeline<P>;
+    pub type HotPipelineRegistry = super::HotPipelineRegistry<P>;
+    pub type HotRayTracePipeline = super::HotRayTracePipeline<P>;
+}
+
+mod registry;
+mod shader;
+
+pub use self::{
+    registry::{
+        HotComputePipeline, HotError, HotGraphicPipeline, HotPipeline, HotPipelineRegistry,
+        HotRayTracePipeline,
+    },
+    shader::{HotShader, HotShaderBuilder},
+};
@@ -0,0 +1,418 @@
+use {
+    super::shader::HotShader,
+    archery::{SharedPointer, SharedPointerKind},
+    log::{info, warn},
+    notify::{recommended_watcher, Event, RecommendedWatcher, RecursiveMode, Watcher},
+    parking_lot::Mutex,
+    screen_13::prelude_all::*,
+    shaderc::Compiler,
+    std::{
+        collections::HashSet,
+        error::Error,
+        fmt::{Debug, Display, Formatter},
+        io,
+        path::PathBuf,
+        sync::mpsc::{channel, Receiver},
+    },
+};
+
+pub type HotComputePipeline<P> = HotPipeline<ComputePipeline<P>, P>;
+pub type HotGraphicPipeline<P> = HotPipeline<GraphicPipeline<P>, P>;
+pub type HotRayTracePipeline<P> = HotPipeline<RayTracePipeline<P>, P>;
+
+/// Describes an error which occurs while building or watching a hot pipeline.
+#[derive(Debug)]
+pub enum HotError {
+    /// The shader source file could not be compiled.
+    Compile(PathBuf, shaderc::Error),
+    /// The compiled shaders could not be used to create a pipeline.
+    Driver(DriverError),
+    /// The shader source file could not be read.
+    Io(PathBuf, io::Error),
+    /// The shader source file could not be watched for changes.
+    Watch(notify::Error),
+}
+
+impl Display for HotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Compile(path, err) => write!(f, "{}: {err}", path.display()),
+            Self::Driver(err) => write!(f, "{err}"),
+            Self::Io(path, err) => write!(f, "{}: {err}", path.display()),
+            Self::Watch(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for HotError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Compile(_, err) => Some(err),
+            Self::Driver(err) => Some(err),
+            Self::Io(_, err) => Some(err),
+            Self::Watch(err) => Some(err),
+        }
+    }
+}
+
+impl From<DriverError> for HotError {
+    fn from(err: DriverError) -> Self {
+        Self::Driver(err)
+    }
+}
+
+/// A handle to a pipeline which is rebuilt by a [`HotPipelineRegistry`] when its shader source
+/// files change.
+///
+/// Clones of a handle share the same pipeline.
+pub struct HotPipeline<T, P>
+where
+    P: SharedPointerKind,
+{
+    pipeline: SharedPointer<Mutex<SharedPointer<T, P>>, P>,
+}
+
+impl<T, P> HotPipeline<T, P>
+where
+    P: SharedPointerKind,
+{
+    fn new(pipeline: T) -> Self {
+        Self {
+            pipeline: SharedPointer::new(Mutex::new(SharedPointer::new(pipeline))),
+        }
+    }
+
+    /// Returns the current pipeline, which should be used for the current frame only.
+    pub fn pipeline(&self) -> SharedPointer<T, P> {
+        SharedPointer::clone(&self.pipeline.lock())
+    }
+
+    fn swap(&self, pipeline: T) {
+        *self.pipeline.lock() = SharedPointer::new(pipeline);
+    }
+}
+
+impl<T, P> Clone for HotPipeline<T, P>
+where
+    P: SharedPointerKind,
+{
+    fn clone(&self) -> Self {
+        Self {
+            pipeline: SharedPointer::clone(&self.pipeline),
+        }
+    }
+}
+
+impl<T, P> Debug for HotPipeline<T, P>
+where
+    P: SharedPointerKind,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("HotPipeline")
+    }
+}
+
+/// Owns the shader source paths and pipeline information of hot pipelines, and rebuilds them when
+/// their shader source files change.
+///
+/// Call [`HotPipelineRegistry::update`] once per frame, before recording any passes, so that
+/// rebuilt pipelines are swapped in at a frame boundary. If a changed shader fails to compile the
+/// previous pipeline remains in use and the errors are returned.
+pub struct HotPipelineRegistry<P>
+where
+    P: SharedPointerKind,
+{
+    compiler: Compiler,
+    device: SharedPointer<Device<P>, P>,
+    entries: Vec<HotEntry<P>>,
+    events: Receiver<notify::Result<Event>>,
+    watched_dirs: HashSet<PathBuf>,
+    watcher: RecommendedWatcher,
+}
+
+impl<P> HotPipelineRegistry<P>
+where
+    P: SharedPointerKind,
+{
+    pub fn new(device: &SharedPointer<Device<P>, P>) -> Result<Self, HotError> {
+        let compiler = Compiler::new().ok_or_else(|| {
+            warn!("unable to create shader compiler");
+
+            HotError::Driver(DriverError::Unsupported)
+        })?;
+        let (tx, events) = channel();
+        let watcher = recommended_watcher(tx).map_err(HotError::Watch)?;
+
+        Ok(Self {
+            compiler,
+            device: SharedPointer::clone(device),
+            entries: vec![],
+            events,
+            watched_dirs: Default::default(),
+            watcher,
+        })
+    }
+
+    /// Creates a compute pipeline which is rebuilt when the shader source files change.
+    pub fn compute_pipeline(
+        &mut self,
+        shader: impl Into<HotShader>,
+    ) -> Result<HotComputePipeline<P>, HotError> {
+        match self.insert(HotEntryPipeline::Compute {
+            pipeline: None,
+            shader: shader.into(),
+        })? {
+            HotEntryPipeline::Compute {
+                pipeline: Some(pipeline),
+                ..
+            } => Ok(pipeline.clone()),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Creates a graphic pipeline which is rebuilt when any of the shader source files change.
+    pub fn graphic_pipeline<S>(
+        &mut self,
+        info: impl Into<GraphicPipelineInfo>,
+        shaders: impl IntoIterator<Item = S>,
+    ) -> Result<HotGraphicPipeline<P>, HotError>
+    where
+        S: Into<HotShader>,
+    {
+        match self.insert(HotEntryPipeline::Graphic {
+            info: info.into(),
+            pipeline: None,
+            shaders: shaders.into_iter().map(Into::into).collect(),
+        })? {
+            HotEntryPipeline::Graphic {
+                pipeline: Some(pipeline),
+                ..
+            } => Ok(pipeline.clone()),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Creates a ray trace pipeline which is rebuilt when any of the shader source files change.
+    pub fn ray_trace_pipeline<S>(
+        &mut self,
+        info: impl Into<RayTracePipelineInfo>,
+        shaders: impl IntoIterator<Item = S>,
+        shader_groups: impl IntoIterator<Item = RayTraceShaderGroup>,
+    ) -> Result<HotRayTracePipeline<P>, HotError>
+    where
+        S: Into<HotShader>,
+    {
+        match self.insert(HotEntryPipeline::RayTrace {
+            info: info.into(),
+            pipeline: None,
+            shader_groups: shader_groups.into_iter().collect(),
+            shaders: shaders.into_iter().map(Into::into).collect(),
+        })? {
+            HotEntryPipeline::RayTrace {
+                pipeline: Some(pipeline),
+                ..
+            } => Ok(pipeline.clone()),
+            _ => unreachable!(),
+        }
+    }
+
+    fn insert(&mut self, pipeline: HotEntryPipeline<P>) -> Result<&HotEntryPipeline<P>, HotError> {
+        let mut entry = HotEntry {
+            files: Default::default(),
+            pipeline,
+        };
+        entry.build(&self.compiler, &self.device)?;
+        self.watch(&entry.files)?;
+        self.entries.push(entry);
+
+        Ok(&self.entries.last().unwrap().pipeline)
+    }
+
+    /// Rebuilds each pipeline which uses a shader source file that has changed since the previous
+    /// update, returning the errors of any pipelines which could not be rebuilt.
+    pub fn update(&mut self) -> Vec<HotError> {
+        let mut changed_files = HashSet::new();
+        let mut res = vec![];
+
+        for event in self.events.try_iter() {
+            match event {
+                Ok(event) if event.kind.is_create() || event.kind.is_modify() => {
+                    changed_files.extend(event.paths)
+                }
+                Ok(_) => (),
+                Err(err) => res.push(HotError::Watch(err)),
+            }
+        }
+
+        if changed_files.is_empty() {
+            return res;
+        }
+
+        let mut files = HashSet::new();
+        for entry in &mut self.entries {
+            if entry.files.is_disjoint(&changed_files) {
+                continue;
+            }
+
+            info!("rebuilding pipeline");
+
+            match entry.build(&self.compiler, &self.device) {
+                Ok(()) => files.extend(entry.files.iter().cloned()),
+                Err(err) => {
+                    warn!("unable to rebuild pipeline: {err}");
+
+                    res.push(err);
+                }
+            }
+        }
+
+        // Shaders may include new files after a change
+        if let Err(err) = self.watch(&files) {
+            res.push(err);
+        }
+
+        res
+    }
+
+    /// Watches the directory of each file, which allows editors to save files by replacing them.
+    fn watch(&mut self, files: &HashSet<PathBuf>) -> Result<(), HotError> {
+        for dir in files.iter().filter_map(|file| file.parent()) {
+            if self.watched_dirs.contains(dir) {
+                continue;
+            }
+
+            self.watcher
+                .watch(dir, RecursiveMode::NonRecursive)
+                .map_err(HotError::Watch)?;
+            self.watched_dirs.insert(dir.to_path_buf());
+        }
+
+        Ok(())
+    }
+}
+
+impl<P> Debug for HotPipelineRegistry<P>
+where
+    P: SharedPointerKind,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("HotPipelineRegistry")
+    }
+}
+
+struct HotEntry<P>
+where
+    P: SharedPointerKind,
+{
+    files: HashSet<PathBuf>,
+    pipeline: HotEntryPipeline<P>,
+}
+
+impl<P> HotEntry<P>
+where
+    P: SharedPointerKind,
+{
+    /// Compiles the shaders and creates the pipeline, swapping it into the existing handle if there
+    /// is one. Nothing is changed if any step fails.
+    fn build(
+        &mut self,
+        compiler: &Compiler,
+        device: &SharedPointer<Device<P>, P>,
+    ) -> Result<(), HotError> {
+        fn compile_all(
+            compiler: &Compiler,
+            shaders: &[HotShader],
+            files: &mut HashSet<PathBuf>,
+        ) -> Result<Vec<Shader>, HotError> {
+            shaders
+                .iter()
+                .map(|shader| {
+                    shader
+                        .compile(compiler, files)
+                        .map(|spirv| shader.shader(&spirv))
+                })
+                .collect()
+        }
+
+        fn swap<T, P>(handle: &mut Option<HotPipeline<T, P>>, pipeline: T)
+        where
+            P: SharedPointerKind,
+        {
+            match handle {
+                Some(handle) => handle.swap(pipeline),
+                None => *handle = Some(HotPipeline::new(pipeline)),
+            }
+        }
+
+        let mut files = HashSet::new();
+
+        match &mut self.pipeline {
+            HotEntryPipeline::Compute { pipeline, shader } => {
+                let spirv = shader.compile(compiler, &mut files)?;
+                let mut info = ComputePipelineInfo::new(spirv.as_slice())
+                    .entry_name(shader.entry_name.clone());
+
+                if let Some(specialization_info) = &shader.specialization_info {
+                    info = info.specialization_info(specialization_info.clone());
+                }
+
+                swap(pipeline, ComputePipeline::create(device, info)?);
+            }
+            HotEntryPipeline::Graphic {
+                info,
+                pipeline,
+                shaders,
+            } => {
+                let shaders = compile_all(compiler, shaders, &mut files)?;
+
+                swap(
+                    pipeline,
+                    GraphicPipeline::create(device, info.clone(), shaders)?,
+                );
+            }
+            HotEntryPipeline::RayTrace {
+                info,
+                pipeline,
+                shader_groups,
+                shaders,
+            } => {
+                let shaders = compile_all(compiler, shaders, &mut files)?;
+
+                swap(
+                    pipeline,
+                    RayTracePipeline::create(
+                        device,
+                        info.clone(),
+                        shaders,
+                        shader_groups.iter().copied(),
+                    )?,
+                );
+            }
+        }
+
+        self.files = files;
+
+        Ok(())
+    }
+}
+
+enum HotEntryPipeline<P>
+where
+    P: SharedPointerKind,
+{
+    Compute {
+        pipeline: Option<HotComputePipeline<P>>,
+        shader: HotShader,
+    },
+    Graphic {
+        info: GraphicPipelineInfo,
+        pipeline: Option<HotGraphicPipeline<P>>,
+        shaders: Vec<HotShader>,
+    },
+    RayTrace {
+        info: RayTracePipelineInfo,
+        pipeline: Option<HotRayTracePipeline<P>>,
+        shader_groups: Vec<RayTraceShaderGroup>,
+        shaders: Vec<HotShader>,
+    },
+}
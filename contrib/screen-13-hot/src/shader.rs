@@ -0,0 +1,251 @@
+use {
+    super::registry::HotError,
+    derive_builder::Builder,
+    screen_13::prelude_all::*,
+    shaderc::{
+        CompileOptions, Compiler, EnvVersion, ResolvedInclude, ShaderKind, SourceLanguage,
+        TargetEnv,
+    },
+    std::{
+        cell::RefCell,
+        collections::{BTreeMap, HashSet},
+        fs::read_to_string,
+        path::{Path, PathBuf},
+    },
+};
+
+/// Returns the canonical form of a path so that file change events may be matched against it.
+fn canonicalize(path: &Path) -> Result<PathBuf, HotError> {
+    path.canonicalize()
+        .map_err(|err| HotError::Io(path.to_path_buf(), err))
+}
+
+fn shader_kind(stage: vk::ShaderStageFlags) -> Option<ShaderKind> {
+    Some(match stage {
+        vk::ShaderStageFlags::ANY_HIT_KHR => ShaderKind::AnyHit,
+        vk::ShaderStageFlags::CALLABLE_KHR => ShaderKind::Callable,
+        vk::ShaderStageFlags::CLOSEST_HIT_KHR => ShaderKind::ClosestHit,
+        vk::ShaderStageFlags::COMPUTE => ShaderKind::Compute,
+        vk::ShaderStageFlags::FRAGMENT => ShaderKind::Fragment,
+        vk::ShaderStageFlags::GEOMETRY => ShaderKind::Geometry,
+        vk::ShaderStageFlags::INTERSECTION_KHR => ShaderKind::Intersection,
+        vk::ShaderStageFlags::MESH_EXT => ShaderKind::Mesh,
+        vk::ShaderStageFlags::MISS_KHR => ShaderKind::Miss,
+        vk::ShaderStageFlags::RAYGEN_KHR => ShaderKind::RayGeneration,
+        vk::ShaderStageFlags::TASK_EXT => ShaderKind::Task,
+        vk::ShaderStageFlags::TESSELLATION_CONTROL => ShaderKind::TessControl,
+        vk::ShaderStageFlags::TESSELLATION_EVALUATION => ShaderKind::TessEvaluation,
+        vk::ShaderStageFlags::VERTEX => ShaderKind::Vertex,
+        _ => return None,
+    })
+}
+
+/// Describes a shader which is compiled from a GLSL or HLSL source file, and compiled again each
+/// time the file or any file it includes changes.
+///
+/// Files with an `hlsl` extension are compiled as HLSL; all other files are compiled as GLSL.
+#[derive(Builder, Clone, Debug)]
+#[builder(build_fn(private, name = "fallible_build"), pattern = "owned")]
+pub struct HotShader {
+    /// The GLSL or HLSL shader entry point name, or `main` by default.
+    #[builder(default = "String::from(\"main\")", setter(into))]
+    pub entry_name: String,
+
+    /// Preprocessor macros defined before compilation, as if declared using `#define`.
+    #[builder(default, setter(custom))]
+    pub macro_definitions: BTreeMap<String, Option<String>>,
+
+    /// The path of the shader source file.
+    #[builder(setter(into))]
+    pub path: PathBuf,
+
+    /// Data about Vulkan specialization constants.
+    #[builder(default, setter(strip_option))]
+    pub specialization_info: Option<SpecializationInfo>,
+
+    pub stage: vk::ShaderStageFlags,
+
+    /// Explicit vertex input layout of this vertex shader; see `Shader::vertex_layout`.
+    #[builder(default, setter(strip_option))]
+    pub vertex_layout: Option<VertexInputState>,
+}
+
+impl HotShader {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(stage: vk::ShaderStageFlags, path: impl Into<PathBuf>) -> HotShaderBuilder {
+        HotShaderBuilder::default().stage(stage).path(path)
+    }
+
+    /// Creates a new ray trace shader.
+    pub fn new_any_hit(path: impl Into<PathBuf>) -> HotShaderBuilder {
+        Self::new(vk::ShaderStageFlags::ANY_HIT_KHR, path)
+    }
+
+    /// Creates a new ray trace shader.
+    pub fn new_callable(path: impl Into<PathBuf>) -> HotShaderBuilder {
+        Self::new(vk::ShaderStageFlags::CALLABLE_KHR, path)
+    }
+
+    /// Creates a new ray trace shader.
+    pub fn new_closest_hit(path: impl Into<PathBuf>) -> HotShaderBuilder {
+        Self::new(vk::ShaderStageFlags::CLOSEST_HIT_KHR, path)
+    }
+
+    /// Creates a new compute shader.
+    pub fn new_compute(path: impl Into<PathBuf>) -> HotShaderBuilder {
+        Self::new(vk::ShaderStageFlags::COMPUTE, path)
+    }
+
+    /// Creates a new fragment shader.
+    pub fn new_fragment(path: impl Into<PathBuf>) -> HotShaderBuilder {
+        Self::new(vk::ShaderStageFlags::FRAGMENT, path)
+    }
+
+    /// Creates a new geometry shader.
+    pub fn new_geometry(path: impl Into<PathBuf>) -> HotShaderBuilder {
+        Self::new(vk::ShaderStageFlags::GEOMETRY, path)
+    }
+
+    /// Creates a new ray trace shader.
+    pub fn new_intersection(path: impl Into<PathBuf>) -> HotShaderBuilder {
+        Self::new(vk::ShaderStageFlags::INTERSECTION_KHR, path)
+    }
+
+    /// Creates a new mesh shader.
+    pub fn new_mesh(path: impl Into<PathBuf>) -> HotShaderBuilder {
+        Self::new(vk::ShaderStageFlags::MESH_EXT, path)
+    }
+
+    /// Creates a new ray trace shader.
+    pub fn new_miss(path: impl Into<PathBuf>) -> HotShaderBuilder {
+        Self::new(vk::ShaderStageFlags::MISS_KHR, path)
+    }
+
+    /// Creates a new ray trace shader.
+    pub fn new_ray_gen(path: impl Into<PathBuf>) -> HotShaderBuilder {
+        Self::new(vk::ShaderStageFlags::RAYGEN_KHR, path)
+    }
+
+    /// Creates a new mesh task shader.
+    pub fn new_task(path: impl Into<PathBuf>) -> HotShaderBuilder {
+        Self::new(vk::ShaderStageFlags::TASK_EXT, path)
+    }
+
+    /// Creates a new tesselation control shader.
+    pub fn new_tesselation_ctrl(path: impl Into<PathBuf>) -> HotShaderBuilder {
+        Self::new(vk::ShaderStageFlags::TESSELLATION_CONTROL, path)
+    }
+
+    /// Creates a new tesselation evaluation shader.
+    pub fn new_tesselation_eval(path: impl Into<PathBuf>) -> HotShaderBuilder {
+        Self::new(vk::ShaderStageFlags::TESSELLATION_EVALUATION, path)
+    }
+
+    /// Creates a new vertex shader.
+    pub fn new_vertex(path: impl Into<PathBuf>) -> HotShaderBuilder {
+        Self::new(vk::ShaderStageFlags::VERTEX, path)
+    }
+
+    /// Compiles the source file into SPIR-V code, adding the canonical path of the source file and
+    /// each included file to `files`.
+    pub(super) fn compile(
+        &self,
+        compiler: &Compiler,
+        files: &mut HashSet<PathBuf>,
+    ) -> Result<Vec<u32>, HotError> {
+        let path = canonicalize(&self.path)?;
+        let source = read_to_string(&path).map_err(|err| HotError::Io(path.clone(), err))?;
+        let kind = shader_kind(self.stage).ok_or(HotError::Driver(DriverError::Unsupported))?;
+        let includes = RefCell::new(vec![]);
+
+        let mut options =
+            CompileOptions::new().ok_or(HotError::Driver(DriverError::Unsupported))?;
+        options.set_target_env(TargetEnv::Vulkan, EnvVersion::Vulkan1_2 as _);
+
+        if path
+            .extension()
+            .map(|ext| ext == "hlsl")
+            .unwrap_or_default()
+        {
+            options.set_source_language(SourceLanguage::HLSL);
+        }
+
+        for (name, value) in &self.macro_definitions {
+            options.add_macro_definition(name, value.as_deref());
+        }
+
+        // Includes are found relative to the including file
+        options.set_include_callback(|name, _, requested_by, _| {
+            let include_path = Path::new(requested_by)
+                .parent()
+                .unwrap_or_else(|| Path::new(""))
+                .join(name)
+                .canonicalize()
+                .map_err(|err| format!("{name}: {err}"))?;
+            let content = read_to_string(&include_path).map_err(|err| format!("{name}: {err}"))?;
+            let resolved_name = include_path.to_string_lossy().into_owned();
+            includes.borrow_mut().push(include_path);
+
+            Ok(ResolvedInclude {
+                resolved_name,
+                content,
+            })
+        });
+
+        let spirv = compiler
+            .compile_into_spirv(
+                &source,
+                kind,
+                &path.to_string_lossy(),
+                &self.entry_name,
+                Some(&options),
+            )
+            .map_err(|err| HotError::Compile(path.clone(), err))?
+            .as_binary()
+            .to_vec();
+
+        drop(options);
+
+        files.insert(path);
+        files.extend(includes.into_inner());
+
+        Ok(spirv)
+    }
+
+    /// Returns a shader using the given compiled code.
+    pub(super) fn shader(&self, spirv: &[u32]) -> Shader {
+        let mut shader = Shader::new(self.stage, spirv).entry_name(self.entry_name.clone());
+
+        if let Some(specialization_info) = &self.specialization_info {
+            shader = shader.specialization_info(specialization_info.clone());
+        }
+
+        if let Some(vertex_layout) = &self.vertex_layout {
+            shader = shader.vertex_layout(vertex_layout.clone());
+        }
+
+        shader.build()
+    }
+}
+
+// HACK: https://github.com/colin-kiegel/rust-derive-builder/issues/56
+impl HotShaderBuilder {
+    /// Defines a preprocessor macro, as if declared using `#define`.
+    pub fn macro_definition(mut self, name: impl Into<String>, value: Option<&str>) -> Self {
+        self.macro_definitions
+            .get_or_insert_with(Default::default)
+            .insert(name.into(), value.map(str::to_owned));
+        self
+    }
+
+    pub fn build(self) -> HotShader {
+        self.fallible_build()
+            .expect("All required fields set at initialization")
+    }
+}
+
+impl From<HotShaderBuilder> for HotShader {
+    fn from(shader: HotShaderBuilder) -> Self {
+        shader.build()
+    }
+}
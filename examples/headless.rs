@@ -0,0 +1,26 @@
+use screen_13::prelude_arc::*;
+
+/// This example renders without a window or display, such as on a continuous integration machine
+/// using a software Vulkan implementation.
+fn main() -> Result<(), DriverError> {
+    pretty_env_logger::init();
+
+    let mut headless = Headless::new(320, 200).build()?;
+
+    // Each frame fades the offscreen image from black to cornflower blue
+    headless.run_frames(60, |frame| {
+        let t = frame.frame_idx as f32 / 59.0;
+        frame
+            .render_graph
+            .clear_color_image_value(frame.image, [0.392 * t, 0.584 * t, 0.929 * t, 1.0]);
+    })?;
+
+    info!(
+        "rendered {}x{} {:?}",
+        headless.width(),
+        headless.height(),
+        headless.image().info.fmt
+    );
+
+    Ok(())
+}
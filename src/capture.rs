@@ -0,0 +1,485 @@
+use {
+    super::{
+        driver::{Buffer, BufferInfo, Device, DriverError},
+        graph::{AnyImageNode, BufferLeaseBinding, RenderGraph},
+        HashPool,
+    },
+    archery::{SharedPointer, SharedPointerKind},
+    ash::vk,
+    log::{info, warn},
+    png::{BitDepth, ColorType, Encoder, EncodingError},
+    std::{
+        collections::VecDeque,
+        fmt::{Debug, Formatter},
+        fs::{create_dir_all, File},
+        io::{self, BufWriter, Write},
+        path::{Path, PathBuf},
+    },
+};
+
+/// An 8-bit RGBA image which has been copied from the GPU, stored in rows from top to bottom.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CaptureImage {
+    pub data: Vec<u8>,
+    pub height: u32,
+    pub width: u32,
+}
+
+impl CaptureImage {
+    /// Writes an 8-bit RGBA PNG file.
+    pub fn write_png(&self, path: impl AsRef<Path>) -> Result<(), EncodingError> {
+        let file = File::create(path)?;
+        let mut encoder = Encoder::new(BufWriter::new(file), self.width, self.height);
+        encoder.set_color(ColorType::Rgba);
+        encoder.set_depth(BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&self.data)
+    }
+
+    /// Writes one frame of a YUV4MPEG2 stream using 4:4:4 BT.601 studio-swing color; alpha is
+    /// ignored.
+    fn write_y4m_frame(&self, writer: &mut impl Write) -> io::Result<()> {
+        let pixel_count = (self.width * self.height) as usize;
+        let mut planes = vec![0u8; pixel_count * 3];
+        let (y_plane, uv_planes) = planes.split_at_mut(pixel_count);
+        let (u_plane, v_plane) = uv_planes.split_at_mut(pixel_count);
+
+        for (idx, pixel) in self.data.chunks_exact(4).enumerate() {
+            let (r, g, b) = (pixel[0] as i32, pixel[1] as i32, pixel[2] as i32);
+
+            y_plane[idx] = (((66 * r + 129 * g + 25 * b + 128) >> 8) + 16) as u8;
+            u_plane[idx] = (((-38 * r - 74 * g + 112 * b + 128) >> 8) + 128) as u8;
+            v_plane[idx] = (((112 * r - 94 * g - 18 * b + 128) >> 8) + 128) as u8;
+        }
+
+        writer.write_all(b"FRAME\n")?;
+        writer.write_all(&planes)
+    }
+}
+
+/// Records every frame presented by an event loop.
+///
+/// While recording, each frame is given a fixed time step of `1 / fps` seconds instead of the
+/// measured frame time, so that the recording plays back smoothly regardless of how long each
+/// frame took to render.
+#[derive(Clone, Debug)]
+pub enum CaptureSequence {
+    /// Writes numbered PNG files (`000000.png`, `000001.png`, ...) into a directory.
+    Png { dir: PathBuf, fps: u32 },
+
+    /// Writes an uncompressed YUV4MPEG2 video file, which can be played or encoded using tools
+    /// such as `ffmpeg`.
+    ///
+    /// _NOTE:_ Frames which are not the same size as the first frame are skipped.
+    Y4m { fps: u32, path: PathBuf },
+}
+
+impl CaptureSequence {
+    fn fps(&self) -> u32 {
+        match self {
+            Self::Png { fps, .. } | Self::Y4m { fps, .. } => *fps,
+        }
+    }
+}
+
+/// The layout of the pixels of a captured image, which are converted to 8-bit RGBA.
+#[derive(Clone, Copy, Debug)]
+enum CaptureFormat {
+    /// 8-bit `BGRA`; swizzled.
+    Bgra8,
+
+    /// 10-bit `RGB` packed into 32 bits with red in the low bits (`A2B10G10R10_*_PACK32`).
+    Rgb10A2,
+
+    /// 10-bit `BGR` packed into 32 bits with blue in the low bits (`A2R10G10B10_*_PACK32`).
+    Bgr10A2,
+
+    /// 8-bit `RGBA`, including `A8B8G8R8_*_PACK32` which has the same byte order on little-endian
+    /// hosts.
+    Rgba8,
+
+    /// 16-bit float `RGBA`; clamped to `0.0..=1.0`.
+    Rgba16Float,
+
+    /// 32-bit float `RGBA`; clamped to `0.0..=1.0`.
+    Rgba32Float,
+}
+
+impl CaptureFormat {
+    fn new(fmt: vk::Format) -> Option<Self> {
+        Some(match fmt {
+            vk::Format::A2B10G10R10_UNORM_PACK32 => Self::Rgb10A2,
+            vk::Format::A2R10G10B10_UNORM_PACK32 => Self::Bgr10A2,
+            vk::Format::A8B8G8R8_SRGB_PACK32
+            | vk::Format::A8B8G8R8_UNORM_PACK32
+            | vk::Format::R8G8B8A8_SRGB
+            | vk::Format::R8G8B8A8_UNORM => Self::Rgba8,
+            vk::Format::B8G8R8A8_SRGB | vk::Format::B8G8R8A8_UNORM => Self::Bgra8,
+            vk::Format::R16G16B16A16_SFLOAT => Self::Rgba16Float,
+            vk::Format::R32G32B32A32_SFLOAT => Self::Rgba32Float,
+            _ => return None,
+        })
+    }
+
+    fn bytes_per_pixel(self) -> vk::DeviceSize {
+        match self {
+            Self::Bgra8 | Self::Bgr10A2 | Self::Rgb10A2 | Self::Rgba8 => 4,
+            Self::Rgba16Float => 8,
+            Self::Rgba32Float => 16,
+        }
+    }
+
+    /// Converts one pixel of this format into 8-bit RGBA.
+    fn to_rgba8(self, pixel: &[u8]) -> [u8; 4] {
+        fn unorm(value: f32) -> u8 {
+            (value.clamp(0.0, 1.0) * 255.0).round() as u8
+        }
+
+        fn unorm10(packed: u32, shift: u32) -> u8 {
+            (((packed >> shift) & 0x3ff) * 255 / 0x3ff) as u8
+        }
+
+        match self {
+            Self::Bgra8 => [pixel[2], pixel[1], pixel[0], pixel[3]],
+            Self::Bgr10A2 | Self::Rgb10A2 => {
+                let packed = u32::from_le_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]);
+                let (red_shift, blue_shift) = if matches!(self, Self::Rgb10A2) {
+                    (0, 20)
+                } else {
+                    (20, 0)
+                };
+
+                [
+                    unorm10(packed, red_shift),
+                    unorm10(packed, 10),
+                    unorm10(packed, blue_shift),
+                    ((packed >> 30) * 255 / 3) as u8,
+                ]
+            }
+            Self::Rgba8 => [pixel[0], pixel[1], pixel[2], pixel[3]],
+            Self::Rgba16Float => {
+                let mut rgba = [0; 4];
+
+                for (channel, bits) in rgba.iter_mut().zip(pixel.chunks_exact(2)) {
+                    *channel = unorm(f16_to_f32(u16::from_le_bytes([bits[0], bits[1]])));
+                }
+
+                rgba
+            }
+            Self::Rgba32Float => {
+                let mut rgba = [0; 4];
+
+                for (channel, bits) in rgba.iter_mut().zip(pixel.chunks_exact(4)) {
+                    *channel = unorm(f32::from_le_bytes([bits[0], bits[1], bits[2], bits[3]]));
+                }
+
+                rgba
+            }
+        }
+    }
+}
+
+enum CaptureOutput {
+    Func(Box<dyn FnOnce(CaptureImage)>),
+    Png(PathBuf),
+    Sequence,
+}
+
+struct PendingCapture<P>
+where
+    P: SharedPointerKind,
+{
+    buffer: BufferLeaseBinding<P>,
+    format: CaptureFormat,
+    height: u32,
+    is_opaque: bool,
+    output: CaptureOutput,
+    width: u32,
+}
+
+impl<P> PendingCapture<P>
+where
+    P: SharedPointerKind,
+{
+    /// Returns the captured image once the GPU has finished the copy and released the buffer.
+    fn try_read(&mut self) -> Option<CaptureImage> {
+        let buffer = self.buffer.get_mut()?;
+        let len = self.width as usize * self.height as usize;
+        let mut data = Vec::with_capacity(len * 4);
+
+        for pixel in Buffer::mapped_slice(buffer)
+            .chunks_exact(self.format.bytes_per_pixel() as _)
+            .take(len)
+        {
+            let mut pixel = self.format.to_rgba8(pixel);
+
+            if self.is_opaque {
+                pixel[3] = 0xff;
+            }
+
+            data.extend_from_slice(&pixel);
+        }
+
+        Some(CaptureImage {
+            data,
+            height: self.height,
+            width: self.width,
+        })
+    }
+}
+
+/// Queued image captures of an event loop, which are delivered in the order they were queued.
+pub(super) struct Captures<P>
+where
+    P: SharedPointerKind,
+{
+    pending: VecDeque<PendingCapture<P>>,
+    pool: HashPool<P>,
+    sequence: Option<SequenceWriter>,
+}
+
+impl<P> Captures<P>
+where
+    P: SharedPointerKind + Send + 'static,
+{
+    pub(super) fn new(
+        device: &SharedPointer<Device<P>, P>,
+        sequence: Option<CaptureSequence>,
+    ) -> Self {
+        Self {
+            pending: Default::default(),
+            pool: HashPool::new(device),
+            sequence: sequence.map(SequenceWriter::new),
+        }
+    }
+
+    /// Returns the fixed time step of the capture sequence, if any.
+    pub(super) fn dt(&self) -> Option<f32> {
+        self.sequence
+            .as_ref()
+            .map(|sequence| 1.0 / sequence.sequence.fps() as f32)
+    }
+
+    /// Queues a capture of the swapchain image if a capture sequence is being recorded.
+    pub(super) fn push_sequence(
+        &mut self,
+        render_graph: &mut RenderGraph<P>,
+        image: impl Into<AnyImageNode<P>>,
+    ) -> Result<(), DriverError> {
+        if self.sequence.is_some() {
+            self.push(render_graph, image, CaptureOutput::Sequence)?;
+        }
+
+        Ok(())
+    }
+
+    /// Queues a capture which calls a function once the GPU has finished the frame.
+    pub(super) fn push_func(
+        &mut self,
+        render_graph: &mut RenderGraph<P>,
+        image: impl Into<AnyImageNode<P>>,
+        func: impl FnOnce(CaptureImage) + 'static,
+    ) -> Result<(), DriverError> {
+        self.push(render_graph, image, CaptureOutput::Func(Box::new(func)))
+    }
+
+    /// Queues a capture which saves a PNG file once the GPU has finished the frame.
+    pub(super) fn push_png(
+        &mut self,
+        render_graph: &mut RenderGraph<P>,
+        image: impl Into<AnyImageNode<P>>,
+        path: PathBuf,
+    ) -> Result<(), DriverError> {
+        self.push(render_graph, image, CaptureOutput::Png(path))
+    }
+
+    fn push(
+        &mut self,
+        render_graph: &mut RenderGraph<P>,
+        image: impl Into<AnyImageNode<P>>,
+        output: CaptureOutput,
+    ) -> Result<(), DriverError> {
+        let image = image.into();
+        let info = render_graph.node_info(image);
+        let format = CaptureFormat::new(info.fmt).ok_or_else(|| {
+            warn!("unsupported capture format {:?}", info.fmt);
+
+            DriverError::Unsupported
+        })?;
+
+        if !info.usage.contains(vk::ImageUsageFlags::TRANSFER_SRC) {
+            warn!("unsupported capture image usage {:?}", info.usage);
+
+            return Err(DriverError::Unsupported);
+        }
+
+        let buffer = render_graph.bind_node(self.pool.lease(BufferInfo::new_mappable(
+            info.width as vk::DeviceSize * info.height as vk::DeviceSize * format.bytes_per_pixel(),
+            vk::BufferUsageFlags::TRANSFER_DST,
+        ))?);
+        render_graph.copy_image_to_buffer(image, buffer);
+
+        self.pending.push_back(PendingCapture {
+            buffer: render_graph.unbind_node(buffer),
+            format,
+            height: info.height,
+
+            // Swapchain images are presented opaque no matter what was written into alpha
+            is_opaque: matches!(image, AnyImageNode::SwapchainImage(_)),
+
+            output,
+            width: info.width,
+        });
+
+        Ok(())
+    }
+
+    /// Delivers all captures which the GPU has finished, stopping at the first unfinished one so
+    /// that captures are always delivered in order.
+    pub(super) fn update(&mut self) {
+        while let Some(pending) = self.pending.front_mut() {
+            let image = if let Some(image) = pending.try_read() {
+                image
+            } else {
+                break;
+            };
+
+            match self.pending.pop_front().unwrap().output {
+                CaptureOutput::Func(func) => func(image),
+                CaptureOutput::Png(path) => {
+                    if let Err(err) = image.write_png(&path) {
+                        warn!("unable to write {}: {err}", path.display());
+                    }
+                }
+                CaptureOutput::Sequence => {
+                    if let Some(sequence) = &mut self.sequence {
+                        sequence.write(&image);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<P> Debug for Captures<P>
+where
+    P: SharedPointerKind,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Captures")
+            .field("pending", &self.pending.len())
+            .field("sequence", &self.sequence.as_ref().map(|seq| &seq.sequence))
+            .finish()
+    }
+}
+
+struct SequenceWriter {
+    frame_idx: usize,
+    sequence: CaptureSequence,
+    y4m: Option<(BufWriter<File>, u32, u32)>,
+}
+
+impl SequenceWriter {
+    fn new(sequence: CaptureSequence) -> Self {
+        Self {
+            frame_idx: 0,
+            sequence,
+            y4m: None,
+        }
+    }
+
+    fn write(&mut self, image: &CaptureImage) {
+        let frame_idx = self.frame_idx;
+        self.frame_idx += 1;
+
+        match &self.sequence {
+            CaptureSequence::Png { dir, .. } => {
+                if frame_idx == 0 {
+                    if let Err(err) = create_dir_all(dir) {
+                        warn!("unable to create {}: {err}", dir.display());
+                    }
+                }
+
+                let path = dir.join(format!("{frame_idx:06}.png"));
+
+                if let Err(err) = image.write_png(&path) {
+                    warn!("unable to write {}: {err}", path.display());
+                }
+            }
+            CaptureSequence::Y4m { fps, path } => {
+                if self.y4m.is_none() {
+                    let writer = File::create(path)
+                        .map(BufWriter::new)
+                        .and_then(|mut writer| {
+                            writeln!(
+                                writer,
+                                "YUV4MPEG2 W{} H{} F{fps}:1 Ip A1:1 C444",
+                                image.width, image.height
+                            )?;
+
+                            Ok(writer)
+                        });
+
+                    match writer {
+                        Ok(writer) => {
+                            info!("recording {}", path.display());
+
+                            self.y4m = Some((writer, image.width, image.height));
+                        }
+                        Err(err) => {
+                            warn!("unable to create {}: {err}", path.display());
+
+                            return;
+                        }
+                    }
+                }
+
+                let (writer, width, height) = self.y4m.as_mut().unwrap();
+
+                if (image.width, image.height) != (*width, *height) {
+                    warn!(
+                        "skipping {}x{} frame {frame_idx} of {}x{} recording",
+                        image.width, image.height, width, height
+                    );
+
+                    return;
+                }
+
+                if let Err(err) = image.write_y4m_frame(writer) {
+                    warn!("unable to write {}: {err}", path.display());
+                }
+            }
+        }
+    }
+}
+
+impl Drop for SequenceWriter {
+    fn drop(&mut self) {
+        if let Some((writer, ..)) = &mut self.y4m {
+            if let Err(err) = writer.flush() {
+                warn!("unable to write recording: {err}");
+            }
+        }
+    }
+}
+
+/// Converts an IEEE 754 half-precision float into a single-precision float.
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = (bits as u32 & 0x8000) << 16;
+    let exponent = (bits as u32 >> 10) & 0x1f;
+    let mantissa = bits as u32 & 0x3ff;
+
+    let bits = match (exponent, mantissa) {
+        (0, 0) => sign,
+        (0, _) => {
+            // Subnormal values are normalized for single precision
+            let shift = mantissa.leading_zeros() - 21;
+            let mantissa = (mantissa << shift) & 0x3ff;
+
+            sign | ((113 - shift) << 23) | (mantissa << 13)
+        }
+        (0x1f, _) => sign | 0x7f80_0000 | (mantissa << 13),
+        _ => sign | ((exponent + 112) << 23) | (mantissa << 13),
+    };
+
+    f32::from_bits(bits)
+}
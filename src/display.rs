@@ -79,9 +79,12 @@ where
         trace!("present_image");
 
         // The swapchain should have been written to, otherwise it would be noise and that's a panic
-        let last_swapchain_access = render_graph
+        render_graph
             .last_write(swapchain_image)
             .expect("uninitialized swapchain image: write something each frame!");
+
+        // The present barrier follows the last access, which may be a read (such as a capture)
+        let last_swapchain_access = render_graph.last_access(swapchain_image).unwrap();
         let mut resolver = render_graph.resolve();
         let wait_dst_stage_mask = resolver.node_pipeline_stages(swapchain_image);
         let swapchain_node = swapchain_image;
@@ -249,6 +252,38 @@ where
             .map_err(|_| ())
     }
 
+    /// Returns the active presentation mode of the swapchain.
+    pub fn present_mode(&self) -> vk::PresentModeKHR {
+        self.swapchain.present_mode()
+    }
+
+    /// Sets the presentation modes of the swapchain, in order of preference; the swapchain is
+    /// recreated when the next image is acquired.
+    pub fn set_present_modes(&mut self, present_modes: impl Into<Vec<vk::PresentModeKHR>>) {
+        self.swapchain.set_present_modes(present_modes);
+    }
+
+    /// Returns the presentation modes supported by the surface.
+    pub fn supported_present_modes(&self) -> Result<Vec<vk::PresentModeKHR>, DriverError> {
+        self.swapchain.supported_present_modes()
+    }
+
+    /// Returns the format and color space of the swapchain images.
+    pub fn swapchain_format(&self) -> vk::SurfaceFormatKHR {
+        self.swapchain.format()
+    }
+
+    /// Waits for all submitted frames to finish, releasing the resources they used.
+    pub fn wait_idle(&mut self) -> Result<(), DisplayError> {
+        for cmd_buf in self.cmd_bufs.iter_mut().flatten() {
+            unsafe {
+                Self::wait_for_fence(cmd_buf)?;
+            }
+        }
+
+        Ok(())
+    }
+
     unsafe fn wait_for_fence(cmd_buf: &mut CommandBuffer<P>) -> Result<(), ()> {
         Device::wait_for_fence(&cmd_buf.device, &cmd_buf.fence).map_err(|_| ())?;
         CommandBuffer::drop_fenced(cmd_buf);
@@ -1,7 +1,8 @@
 use {
-    super::{Device, DriverError},
+    super::{shader::format_texel_size, BlockLayout, BlockMember, Device, DriverError},
     archery::{SharedPointer, SharedPointerKind},
     ash::vk,
+    bytemuck::{bytes_of, Pod},
     derive_builder::Builder,
     gpu_allocator::{
         vulkan::{Allocation, AllocationCreateDesc},
@@ -9,8 +10,11 @@ use {
     },
     log::trace,
     log::warn,
+    parking_lot::Mutex,
     std::{
+        collections::{hash_map::Entry, HashMap},
         fmt::{Debug, Formatter},
+        mem::size_of,
         ops::{Deref, Range},
         thread::panicking,
     },
@@ -22,6 +26,7 @@ where
 {
     allocation: Option<Allocation>,
     buffer: vk::Buffer,
+    buffer_view_cache: Mutex<HashMap<BufferViewInfo, BufferView<P>>>,
     device: SharedPointer<Device<P>, P>,
     pub info: BufferInfo,
     pub name: Option<String>,
@@ -99,17 +104,71 @@ where
         Ok(Self {
             allocation: Some(allocation),
             buffer,
+            buffer_view_cache: Default::default(),
             device,
             info,
             name: None,
         })
     }
 
+    /// Copies a typed value into a uniform or storage block which starts at `offset` of this
+    /// buffer, such as a block from `GraphicPipeline::descriptor_blocks`.
+    ///
+    /// _NOTE:_ In debug builds, panics if the size or field offsets of `T` do not match the block.
+    pub fn copy_block<T>(this: &mut Self, offset: vk::DeviceSize, block: &[BlockMember], data: &T)
+    where
+        T: BlockLayout,
+    {
+        debug_assert!(
+            T::validate_block(block).is_ok(),
+            "data does not match the shader block"
+        );
+
+        Self::copy_from_slice(this, offset, bytes_of(data));
+    }
+
+    /// Copies a value into a single member of a uniform or storage block which starts at `offset`
+    /// of this buffer. The member is found using its name in the shader code (such as
+    /// `light.color`).
+    ///
+    /// _NOTE:_ Panics if the member is not found, or in debug builds if the size of `T` does not
+    /// match the member.
+    pub fn copy_block_member<T>(
+        this: &mut Self,
+        offset: vk::DeviceSize,
+        block: &[BlockMember],
+        name: &str,
+        data: &T,
+    ) where
+        T: Pod,
+    {
+        let member = block
+            .iter()
+            .find(|member| member.name == name)
+            .unwrap_or_else(|| panic!("block member `{name}` not found"));
+
+        // Runtime-sized arrays have no reflected size
+        debug_assert!(
+            member.size == 0 || member.size == size_of::<T>() as u32,
+            "block member `{name}` size mismatch"
+        );
+
+        Self::copy_from_slice(
+            this,
+            offset + member.offset as vk::DeviceSize,
+            bytes_of(data),
+        );
+    }
+
     pub fn copy_from_slice(this: &mut Self, offset: vk::DeviceSize, slice: &[u8]) {
         Self::mapped_slice_mut(this)[offset as _..offset as usize + slice.len()]
             .copy_from_slice(slice);
     }
 
+    pub fn create_view(this: &Self, info: BufferViewInfo) -> Result<BufferView<P>, DriverError> {
+        BufferView::create(&this.device, info, this)
+    }
+
     pub fn device_address(this: &Self) -> vk::DeviceAddress {
         unsafe {
             this.device.get_buffer_device_address(
@@ -118,6 +177,12 @@ where
         }
     }
 
+    /// Returns the mapped memory of a buffer created with `can_map`, such as for reading data
+    /// written by the GPU.
+    pub fn mapped_slice(this: &Self) -> &[u8] {
+        &this.allocation.as_ref().unwrap().mapped_slice().unwrap()[0..this.info.size as usize]
+    }
+
     pub fn mapped_slice_mut(this: &mut Self) -> &mut [u8] {
         &mut this
             .allocation
@@ -126,6 +191,16 @@ where
             .mapped_slice_mut()
             .unwrap()[0..this.info.size as usize]
     }
+
+    /// Returns a texel buffer view of this buffer, creating and caching it if required.
+    pub fn view_ref(this: &Self, info: BufferViewInfo) -> Result<vk::BufferView, DriverError> {
+        let mut buffer_view_cache = this.buffer_view_cache.lock();
+
+        Ok(match buffer_view_cache.entry(info) {
+            Entry::Occupied(entry) => **entry.get(),
+            Entry::Vacant(entry) => **entry.insert(Self::create_view(this, info)?),
+        })
+    }
 }
 
 impl<P> Debug for Buffer<P>
@@ -161,6 +236,8 @@ where
             return;
         }
 
+        self.buffer_view_cache.lock().clear();
+
         self.device
             .allocator
             .as_ref()
@@ -253,8 +330,183 @@ impl From<Option<Range<vk::DeviceSize>>> for BufferSubresource {
     }
 }
 
+impl From<BufferViewInfo> for BufferSubresource {
+    fn from(info: BufferViewInfo) -> Self {
+        Self {
+            start: info.start,
+            end: info.end,
+        }
+    }
+}
+
 impl From<BufferSubresource> for Range<vk::DeviceSize> {
     fn from(subresource: BufferSubresource) -> Self {
         subresource.start..subresource.end
     }
 }
+
+/// Smart pointer handle to a [buffer view] object, used by texel buffer descriptors.
+///
+/// [buffer view]: https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/VkBufferView.html
+#[derive(Debug)]
+pub struct BufferView<P>
+where
+    P: SharedPointerKind,
+{
+    buffer_view: vk::BufferView,
+    device: SharedPointer<Device<P>, P>,
+    pub info: BufferViewInfo,
+}
+
+impl<P> BufferView<P>
+where
+    P: SharedPointerKind,
+{
+    pub fn create(
+        device: &SharedPointer<Device<P>, P>,
+        info: impl Into<BufferViewInfo>,
+        buffer: &Buffer<P>,
+    ) -> Result<Self, DriverError> {
+        let info = info.into();
+
+        trace!("create: {:?}", info);
+
+        if info.fmt == vk::Format::UNDEFINED {
+            warn!("buffer views require a format");
+
+            return Err(DriverError::InvalidData);
+        }
+
+        debug_assert!(
+            buffer.info.usage.intersects(
+                vk::BufferUsageFlags::STORAGE_TEXEL_BUFFER
+                    | vk::BufferUsageFlags::UNIFORM_TEXEL_BUFFER
+            ),
+            "buffer usage must include a texel buffer flag"
+        );
+
+        let limits = &device.physical_device.props.limits;
+
+        if info.start % limits.min_texel_buffer_offset_alignment != 0 {
+            warn!(
+                "buffer view offset {} is not a multiple of minTexelBufferOffsetAlignment ({})",
+                info.start, limits.min_texel_buffer_offset_alignment
+            );
+
+            return Err(DriverError::InvalidData);
+        }
+
+        let range = if info.end == vk::WHOLE_SIZE {
+            buffer.info.size.saturating_sub(info.start)
+        } else {
+            info.end - info.start
+        };
+
+        if let Some(texel_size) = format_texel_size(info.fmt) {
+            let elements = range / texel_size as vk::DeviceSize;
+
+            if elements > limits.max_texel_buffer_elements as vk::DeviceSize {
+                warn!(
+                    "buffer view of {elements} {:?} texels exceeds maxTexelBufferElements ({})",
+                    info.fmt, limits.max_texel_buffer_elements
+                );
+
+                return Err(DriverError::InvalidData);
+            }
+        }
+
+        let device = SharedPointer::clone(device);
+        let create_info = vk::BufferViewCreateInfo::builder()
+            .buffer(**buffer)
+            .format(info.fmt)
+            .offset(info.start)
+            .range(if info.end == vk::WHOLE_SIZE {
+                vk::WHOLE_SIZE
+            } else {
+                range
+            });
+        let buffer_view =
+            unsafe { device.create_buffer_view(&create_info, None) }.map_err(|err| {
+                warn!("{err}");
+
+                DriverError::Unsupported
+            })?;
+
+        Ok(Self {
+            buffer_view,
+            device,
+            info,
+        })
+    }
+}
+
+impl<P> Deref for BufferView<P>
+where
+    P: SharedPointerKind,
+{
+    type Target = vk::BufferView;
+
+    fn deref(&self) -> &Self::Target {
+        &self.buffer_view
+    }
+}
+
+impl<P> Drop for BufferView<P>
+where
+    P: SharedPointerKind,
+{
+    fn drop(&mut self) {
+        if panicking() {
+            return;
+        }
+
+        unsafe {
+            self.device.destroy_buffer_view(self.buffer_view, None);
+        }
+    }
+}
+
+/// Information about a range of a buffer as seen by a descriptor.
+///
+/// Texel buffer descriptors (`samplerBuffer`, `imageBuffer`, etc.) require a format; all other
+/// buffer descriptors use `vk::Format::UNDEFINED` and only the range is used.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BufferViewInfo {
+    pub fmt: vk::Format,
+    pub start: vk::DeviceSize,
+    pub end: vk::DeviceSize,
+}
+
+impl BufferViewInfo {
+    pub fn new(fmt: vk::Format, range: Range<vk::DeviceSize>) -> Self {
+        Self {
+            fmt,
+            start: range.start,
+            end: range.end,
+        }
+    }
+}
+
+impl From<BufferInfo> for BufferViewInfo {
+    fn from(info: BufferInfo) -> Self {
+        BufferSubresource::from(info).into()
+    }
+}
+
+impl From<BufferSubresource> for BufferViewInfo {
+    fn from(subresource: BufferSubresource) -> Self {
+        Self::new(vk::Format::UNDEFINED, subresource.start..subresource.end)
+    }
+}
+
+impl From<Range<vk::DeviceSize>> for BufferViewInfo {
+    fn from(range: Range<vk::DeviceSize>) -> Self {
+        Self::new(vk::Format::UNDEFINED, range)
+    }
+}
+
+impl From<(vk::Format, Range<vk::DeviceSize>)> for BufferViewInfo {
+    fn from((fmt, range): (vk::Format, Range<vk::DeviceSize>)) -> Self {
+        Self::new(fmt, range)
+    }
+}
@@ -0,0 +1,418 @@
+//! Runtime compilation of GLSL and WGSL shader source code, using `naga`.
+
+use {
+    super::{Shader, ShaderBuilder},
+    ash::vk,
+    derive_builder::Builder,
+    log::warn,
+    naga::{
+        back::spv,
+        front::{glsl, wgsl},
+        valid::{Capabilities, ValidationFlags, Validator},
+        Module, ShaderStage,
+    },
+    std::{
+        collections::BTreeMap,
+        error::Error,
+        fmt::{Display, Formatter},
+        fs::read_to_string,
+        path::{Path, PathBuf},
+    },
+};
+
+fn include_name(line: &str) -> Option<&str> {
+    let name = line
+        .trim_start()
+        .strip_prefix('#')?
+        .trim_start()
+        .strip_prefix("include")?
+        .trim();
+
+    name.strip_prefix('"')
+        .and_then(|name| name.strip_suffix('"'))
+        .or_else(|| {
+            name.strip_prefix('<')
+                .and_then(|name| name.strip_suffix('>'))
+        })
+}
+
+fn naga_stage(stage: vk::ShaderStageFlags) -> Result<ShaderStage, ShaderCompileError> {
+    match stage {
+        vk::ShaderStageFlags::COMPUTE => Ok(ShaderStage::Compute),
+        vk::ShaderStageFlags::FRAGMENT => Ok(ShaderStage::Fragment),
+        vk::ShaderStageFlags::VERTEX => Ok(ShaderStage::Vertex),
+        _ => Err(ShaderDiagnostic {
+            column: 0,
+            line: 0,
+            message: format!("unsupported shader stage {stage:?}"),
+            path: None,
+        }
+        .into()),
+    }
+}
+
+/// The error returned when shader source code cannot be compiled.
+#[derive(Debug)]
+pub struct ShaderCompileError {
+    /// Each problem found in the shader source code.
+    pub diagnostics: Vec<ShaderDiagnostic>,
+}
+
+impl Display for ShaderCompileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (idx, diagnostic) in self.diagnostics.iter().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{diagnostic}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for ShaderCompileError {}
+
+impl From<ShaderDiagnostic> for ShaderCompileError {
+    fn from(diagnostic: ShaderDiagnostic) -> Self {
+        Self {
+            diagnostics: vec![diagnostic],
+        }
+    }
+}
+
+/// Information used to compile shader source code at runtime.
+#[derive(Builder, Clone, Debug)]
+#[builder(build_fn(private, name = "fallible_build"), pattern = "owned")]
+pub struct ShaderCompileInfo {
+    /// Preprocessor definitions injected into GLSL source code, as if declared using `#define`.
+    ///
+    /// _NOTE:_ WGSL source code does not support preprocessor definitions.
+    #[builder(default, setter(custom))]
+    pub defines: BTreeMap<String, String>,
+
+    /// The WGSL shader entry point name, or `main` by default.
+    ///
+    /// _NOTE:_ GLSL source code always uses `main`.
+    #[builder(default = "String::from(\"main\")", setter(into))]
+    pub entry_name: String,
+
+    /// Directories searched, in order, for `#include` files which are not found relative to the
+    /// including file.
+    #[builder(default, setter(custom))]
+    pub include_dirs: Vec<PathBuf>,
+
+    /// The path of the source code, used to find relative `#include` files and in diagnostics.
+    #[builder(default, setter(into, strip_option))]
+    pub path: Option<PathBuf>,
+}
+
+impl ShaderCompileInfo {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> ShaderCompileInfoBuilder {
+        ShaderCompileInfoBuilder::default()
+    }
+}
+
+impl Default for ShaderCompileInfo {
+    fn default() -> Self {
+        Self::new().build()
+    }
+}
+
+impl From<ShaderCompileInfoBuilder> for ShaderCompileInfo {
+    fn from(info: ShaderCompileInfoBuilder) -> Self {
+        info.build()
+    }
+}
+
+// HACK: https://github.com/colin-kiegel/rust-derive-builder/issues/56
+impl ShaderCompileInfoBuilder {
+    /// Injects a preprocessor definition into GLSL source code.
+    pub fn define(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.defines
+            .get_or_insert_with(Default::default)
+            .insert(name.into(), value.into());
+        self
+    }
+
+    /// Adds a directory searched for `#include` files.
+    pub fn include_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.include_dirs
+            .get_or_insert_with(Default::default)
+            .push(path.into());
+        self
+    }
+
+    pub fn build(self) -> ShaderCompileInfo {
+        self.fallible_build()
+            .expect("All required fields set at initialization")
+    }
+}
+
+/// A problem found in shader source code.
+#[derive(Clone, Debug)]
+pub struct ShaderDiagnostic {
+    /// The one-based column of the problem, or zero if unknown.
+    pub column: usize,
+
+    /// The one-based line of the problem, or zero if unknown.
+    pub line: usize,
+
+    /// A description of the problem.
+    pub message: String,
+
+    /// The path of the file which contains the problem, or `None` for the source code given
+    /// without a path.
+    pub path: Option<PathBuf>,
+}
+
+impl Display for ShaderDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "{}", path.display())?,
+            None => f.write_str("<source>")?,
+        }
+
+        if self.line > 0 {
+            write!(f, ":{}:{}", self.line, self.column)?;
+        }
+
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl Shader {
+    /// Compiles GLSL source code into a new shader.
+    ///
+    /// Source lines of the form `#include "file"` or `#include <file>` are replaced by the contents
+    /// of the named file.
+    pub fn from_glsl(
+        stage: vk::ShaderStageFlags,
+        source: &str,
+        info: impl Into<ShaderCompileInfo>,
+    ) -> Result<ShaderBuilder, ShaderCompileError> {
+        let info = info.into();
+
+        Self::compile(stage, source, &info, |shader_stage, source| {
+            let mut options = glsl::Options::from(shader_stage);
+            options.defines.extend(info.defines.clone());
+
+            let module = glsl::Parser::default()
+                .parse(&options, &source.text)
+                .map_err(|errs| ShaderCompileError {
+                    diagnostics: errs
+                        .into_iter()
+                        .map(|err| {
+                            source.diagnostic(
+                                err.meta.to_range().map(|range| range.start),
+                                err.kind.to_string(),
+                            )
+                        })
+                        .collect(),
+                })?;
+
+            // GLSL written for Vulkan already uses the Vulkan coordinate space
+            let mut options = spv::Options::default();
+            options
+                .flags
+                .remove(spv::WriterFlags::ADJUST_COORDINATE_SPACE);
+
+            source.write_spirv(&module, &options, shader_stage, "main")
+        })
+        .map(|spirv| Self::new(stage, spirv))
+    }
+
+    /// Compiles WGSL source code into a new shader.
+    ///
+    /// Source lines of the form `#include "file"` or `#include <file>` are replaced by the contents
+    /// of the named file.
+    pub fn from_wgsl(
+        stage: vk::ShaderStageFlags,
+        source: &str,
+        info: impl Into<ShaderCompileInfo>,
+    ) -> Result<ShaderBuilder, ShaderCompileError> {
+        let info = info.into();
+
+        Self::compile(stage, source, &info, |shader_stage, source| {
+            let module = wgsl::parse_str(&source.text).map_err(|err| {
+                let mut diagnostics = err
+                    .labels()
+                    .map(|(span, label)| {
+                        source.diagnostic(
+                            span.to_range().map(|range| range.start),
+                            format!("{}: {label}", err.message()),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+
+                if diagnostics.is_empty() {
+                    diagnostics.push(source.diagnostic(None, err.message()));
+                }
+
+                ShaderCompileError { diagnostics }
+            })?;
+
+            source.write_spirv(
+                &module,
+                &spv::Options::default(),
+                shader_stage,
+                &info.entry_name,
+            )
+        })
+        .map(|spirv| Self::new(stage, spirv).entry_name(info.entry_name))
+    }
+
+    fn compile(
+        stage: vk::ShaderStageFlags,
+        source: &str,
+        info: &ShaderCompileInfo,
+        compile_fn: impl FnOnce(ShaderStage, &Source) -> Result<Vec<u32>, ShaderCompileError>,
+    ) -> Result<Vec<u32>, ShaderCompileError> {
+        naga_stage(stage)
+            .and_then(|shader_stage| {
+                let source = Source::preprocess(source, info)?;
+
+                compile_fn(shader_stage, &source)
+            })
+            .map_err(|err| {
+                warn!("unable to compile shader:\n{err}");
+
+                err
+            })
+    }
+}
+
+/// Preprocessed source code which tracks the file and line each line of text came from.
+struct Source {
+    lines: Vec<(Option<PathBuf>, usize)>,
+    text: String,
+}
+
+impl Source {
+    fn diagnostic(&self, offset: Option<usize>, message: impl Into<String>) -> ShaderDiagnostic {
+        let (path, line, column) = offset
+            .map(|offset| {
+                let text = self.text.get(..offset).unwrap_or(&self.text);
+                let line_start = text.rfind('\n').map(|idx| idx + 1).unwrap_or_default();
+                let (path, line) = self
+                    .lines
+                    .get(text.matches('\n').count())
+                    .cloned()
+                    .unwrap_or_default();
+
+                (path, line, text.len() - line_start + 1)
+            })
+            .unwrap_or_default();
+
+        ShaderDiagnostic {
+            column,
+            line,
+            message: message.into(),
+            path,
+        }
+    }
+
+    fn include(
+        &mut self,
+        path: Option<&Path>,
+        source: &str,
+        include_dirs: &[PathBuf],
+        stack: &mut Vec<PathBuf>,
+    ) -> Result<(), ShaderCompileError> {
+        for (idx, line) in source.lines().enumerate() {
+            let name = match include_name(line) {
+                Some(name) => name,
+                None => {
+                    self.lines.push((path.map(Path::to_path_buf), idx + 1));
+                    self.text.push_str(line);
+                    self.text.push('\n');
+
+                    continue;
+                }
+            };
+            let diagnostic = |message: String| ShaderDiagnostic {
+                column: 1,
+                line: idx + 1,
+                message,
+                path: path.map(Path::to_path_buf),
+            };
+            let include_path = path
+                .and_then(Path::parent)
+                .into_iter()
+                .chain(include_dirs.iter().map(PathBuf::as_path))
+                .map(|dir| dir.join(name))
+                .find(|include_path| include_path.is_file())
+                .ok_or_else(|| diagnostic(format!("include file `{name}` not found")))?;
+
+            // Paths are compared in canonical form so that `a/../b.glsl` and `b.glsl` match
+            let canonical_path = include_path
+                .canonicalize()
+                .map_err(|err| diagnostic(format!("unable to read `{name}`: {err}")))?;
+
+            if stack.contains(&canonical_path) {
+                return Err(diagnostic(format!("recursive include of `{name}`")).into());
+            }
+
+            let include_source = read_to_string(&include_path)
+                .map_err(|err| diagnostic(format!("unable to read `{name}`: {err}")))?;
+
+            stack.push(canonical_path);
+            self.include(Some(&include_path), &include_source, include_dirs, stack)?;
+            stack.pop();
+        }
+
+        Ok(())
+    }
+
+    fn preprocess(source: &str, info: &ShaderCompileInfo) -> Result<Self, ShaderCompileError> {
+        let mut res = Self {
+            lines: vec![],
+            text: String::with_capacity(source.len()),
+        };
+        res.include(
+            info.path.as_deref(),
+            source,
+            &info.include_dirs,
+            &mut info
+                .path
+                .iter()
+                .map(|path| path.canonicalize().unwrap_or_else(|_| path.clone()))
+                .collect(),
+        )?;
+
+        Ok(res)
+    }
+
+    fn write_spirv(
+        &self,
+        module: &Module,
+        options: &spv::Options,
+        shader_stage: ShaderStage,
+        entry_name: &str,
+    ) -> Result<Vec<u32>, ShaderCompileError> {
+        let module_info = Validator::new(ValidationFlags::all(), Capabilities::all())
+            .validate(module)
+            .map_err(|err| {
+                let offset = err
+                    .spans()
+                    .next()
+                    .and_then(|(span, _)| span.to_range())
+                    .map(|range| range.start);
+
+                self.diagnostic(offset, err.as_inner().to_string())
+            })?;
+
+        spv::write_vec(
+            module,
+            &module_info,
+            options,
+            Some(&spv::PipelineOptions {
+                entry_point: entry_name.to_owned(),
+                shader_stage,
+            }),
+        )
+        .map_err(|err| self.diagnostic(None, err.to_string()).into())
+    }
+}
@@ -1,13 +1,19 @@
 use {
     super::{
-        shader::ShaderCode, DescriptorBindingMap, Device, DriverError, PipelineDescriptorInfo,
-        Shader, SpecializationInfo,
+        shader::ShaderCode, BlockMember, DescriptorBinding, DescriptorBindingMap,
+        DescriptorBlockMap, DescriptorNameMap, Device, DriverError, PipelineDescriptorInfo,
+        SamplerInfo, Shader, SpecializationConstantMap, SpecializationInfo, SpecializationValue,
     },
     archery::{SharedPointer, SharedPointerKind},
     ash::vk,
     derive_builder::Builder,
     log::{trace, warn},
-    std::{ffi::CString, ops::Deref, thread::panicking},
+    std::{
+        collections::{BTreeMap, HashMap},
+        ffi::CString,
+        ops::Deref,
+        thread::panicking,
+    },
 };
 
 #[derive(Debug)]
@@ -16,11 +22,14 @@ where
     P: SharedPointerKind,
 {
     pub descriptor_bindings: DescriptorBindingMap,
+    pub descriptor_blocks: DescriptorBlockMap,
     pub descriptor_info: PipelineDescriptorInfo<P>,
+    pub descriptor_names: DescriptorNameMap,
     pub device: SharedPointer<Device<P>, P>,
     pub layout: vk::PipelineLayout,
     pub info: ComputePipelineInfo,
     pipeline: vk::Pipeline,
+    pub push_constant_block: Vec<BlockMember>,
     pub push_constants: Option<vk::PushConstantRange>,
 }
 
@@ -41,8 +50,10 @@ where
         let shader = info.clone().into_shader();
 
         // Use SPIR-V reflection to get the types and counts of all descriptors
-        let descriptor_bindings = shader.descriptor_bindings(&device);
+        let descriptor_bindings = shader.descriptor_bindings(&device)?;
+        let descriptor_blocks = shader.descriptor_blocks();
         let descriptor_info = PipelineDescriptorInfo::create(&device, &descriptor_bindings)?;
+        let descriptor_names = shader.descriptor_names();
         let descriptor_set_layouts = descriptor_info
             .layouts
             .iter()
@@ -81,6 +92,7 @@ where
             let mut layout_info =
                 vk::PipelineLayoutCreateInfo::builder().set_layouts(&descriptor_set_layouts);
 
+            let push_constant_block = shader.push_constant_block();
             let push_constants = shader.push_constant_range();
             if let Some(push_constants) = &push_constants {
                 layout_info = layout_info.push_constant_ranges(from_ref(push_constants));
@@ -98,7 +110,7 @@ where
                 .layout(layout);
             let pipeline = device
                 .create_compute_pipelines(
-                    vk::PipelineCache::null(),
+                    device.pipeline_cache,
                     from_ref(&pipeline_info.build()),
                     None,
                 )
@@ -112,11 +124,14 @@ where
 
             Ok(ComputePipeline {
                 descriptor_bindings,
+                descriptor_blocks,
                 descriptor_info,
+                descriptor_names,
                 device,
                 info,
                 layout,
                 pipeline,
+                push_constant_block,
                 push_constants,
             })
         }
@@ -157,6 +172,11 @@ pub struct ComputePipelineInfo {
     #[builder(setter(strip_option), default = "String::from(\"main\")")]
     pub entry_name: String,
 
+    /// Explicit samplers used by combined image sampler bindings, instead of the samplers guessed
+    /// from binding names.
+    #[builder(default, setter(custom))]
+    pub image_samplers: BTreeMap<DescriptorBinding, SamplerInfo>,
+
     /// A descriptive name used in debugging messages.
     #[builder(default, setter(strip_option))]
     pub name: Option<String>,
@@ -179,6 +199,10 @@ impl ComputePipelineInfo {
         let mut shader =
             Shader::new(vk::ShaderStageFlags::COMPUTE, self.spirv).entry_name(self.entry_name);
 
+        for (descriptor_binding, sampler_info) in self.image_samplers {
+            shader = shader.image_sampler(descriptor_binding, sampler_info);
+        }
+
         if let Some(specialization_info) = self.specialization_info {
             shader = shader.specialization_info(specialization_info);
         }
@@ -187,6 +211,96 @@ impl ComputePipelineInfo {
     }
 }
 
+impl ComputePipelineInfoBuilder {
+    /// Specifies the sampler used by a combined image sampler binding, instead of the sampler
+    /// guessed from the binding name.
+    pub fn image_sampler(
+        mut self,
+        descriptor_binding: DescriptorBinding,
+        info: impl Into<SamplerInfo>,
+    ) -> Self {
+        self.image_samplers
+            .get_or_insert_with(Default::default)
+            .insert(descriptor_binding, info.into());
+        self
+    }
+
+    /// Sets the value of a specialization constant, found using its name in the shader code.
+    ///
+    /// _NOTE:_ Panics if the constant is not found or if the type of `value` does not match the
+    /// declared type of the constant.
+    pub fn specialize(mut self, name: &str, value: impl Into<SpecializationValue>) -> Self {
+        self.specialization_info = Some(Some(
+            SpecializationInfo::specialize_named(
+                self.entry_name.as_deref().unwrap_or("main"),
+                self.spirv.as_deref().unwrap(),
+                self.specialization_info.flatten(),
+                name,
+                value.into(),
+            )
+            .expect("invalid specialization constant"),
+        ));
+        self
+    }
+}
+
+/// Compute pipelines created from the same shader code using different specialization constant
+/// values.
+///
+/// Each variant is created the first time it is requested and cached for later requests.
+#[derive(Debug)]
+pub struct ComputePipelineVariants<P>
+where
+    P: SharedPointerKind,
+{
+    device: SharedPointer<Device<P>, P>,
+    info: ComputePipelineInfo,
+    pipelines: HashMap<SpecializationInfo, SharedPointer<ComputePipeline<P>, P>>,
+    spec_constants: SpecializationConstantMap,
+}
+
+impl<P> ComputePipelineVariants<P>
+where
+    P: SharedPointerKind,
+{
+    pub fn new(device: &SharedPointer<Device<P>, P>, info: impl Into<ComputePipelineInfo>) -> Self {
+        let info: ComputePipelineInfo = info.into();
+        let spec_constants = info.clone().into_shader().specialization_constants();
+
+        Self {
+            device: SharedPointer::clone(device),
+            info,
+            pipelines: Default::default(),
+            spec_constants,
+        }
+    }
+
+    /// Returns the pipeline variant which uses the given named specialization constant values, in
+    /// addition to the specialization info of the pipeline info.
+    pub fn pipeline<'a>(
+        &mut self,
+        constants: impl IntoIterator<Item = (&'a str, SpecializationValue)>,
+    ) -> Result<SharedPointer<ComputePipeline<P>, P>, DriverError> {
+        let mut specialization_info = self.info.specialization_info.clone().unwrap_or_default();
+        for (name, value) in constants {
+            specialization_info.set_named(&self.spec_constants, name, value)?;
+        }
+
+        if let Some(pipeline) = self.pipelines.get(&specialization_info) {
+            return Ok(SharedPointer::clone(pipeline));
+        }
+
+        let mut info = self.info.clone();
+        info.specialization_info = Some(specialization_info.clone());
+
+        let pipeline = SharedPointer::new(ComputePipeline::create(&self.device, info)?);
+        self.pipelines
+            .insert(specialization_info, SharedPointer::clone(&pipeline));
+
+        Ok(pipeline)
+    }
+}
+
 impl<S> From<S> for ComputePipelineInfo
 where
     S: ShaderCode,
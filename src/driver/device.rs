@@ -1,10 +1,14 @@
 use {
     super::{
-        DriverConfig, DriverError, Instance, PhysicalDevice,
-        PhysicalDeviceRayTracePipelineProperties, QueueFamily, SamplerDesc, Surface,
+        worker::WorkerPool, DriverConfig, DriverError, Instance, PhysicalDevice,
+        PhysicalDeviceMultiviewProperties, PhysicalDeviceRayTracePipelineProperties, QueueFamily,
+        Sampler, SamplerInfo, Surface,
     },
     archery::{SharedPointer, SharedPointerKind},
-    ash::{extensions::khr, vk},
+    ash::{
+        extensions::{ext, khr},
+        vk,
+    },
     gpu_allocator::{
         vulkan::{Allocator, AllocatorCreateDesc},
         AllocatorDebugSettings,
@@ -31,14 +35,31 @@ where
     pub accel_struct_ext: Option<khr::AccelerationStructure>,
     pub(super) allocator: Option<Mutex<Allocator>>,
     device: ash::Device,
-    immutable_samplers: HashMap<SamplerDesc, vk::Sampler>,
+
+    /// Extended dynamic state (cull mode, front face, and depth test), or `None` if
+    /// `VK_EXT_extended_dynamic_state` is not supported.
+    pub extended_dynamic_state_ext: Option<ext::ExtendedDynamicState>,
+
+    immutable_samplers: Mutex<HashMap<SamplerInfo, vk::Sampler>>,
     pub instance: SharedPointer<Instance, P>, // TODO: Need shared?
+    pub mesh_shader_ext: Option<ext::MeshShader>,
+
+    /// Multiview limits of the device, or `None` if multiview rendering is not supported.
+    pub multiview_properties: Option<PhysicalDeviceMultiviewProperties>,
     pub physical_device: PhysicalDevice,
+
+    /// The pipeline cache used when creating all pipelines of this device; see
+    /// `DriverConfig::pipeline_cache_data` and [`Device::pipeline_cache_data`].
+    pub(super) pipeline_cache: vk::PipelineCache,
+
     pub queue: Queue,
     pub ray_tracing_pipeline_ext: Option<khr::RayTracingPipeline>,
     pub ray_tracing_pipeline_properties: Option<PhysicalDeviceRayTracePipelineProperties>,
     pub surface_ext: Option<khr::Surface>,
     pub swapchain_ext: Option<khr::Swapchain>,
+
+    /// Threads which create pipelines in the background, such as when they are prewarmed.
+    pub(super) workers: WorkerPool,
 }
 
 impl<P> Device<P>
@@ -85,9 +106,10 @@ where
         let get_physical_device_properties2 = fp_v1_1.get_physical_device_properties2;
 
         let features = cfg.features();
-        let device_extension_names = features.extension_names();
+        let mut device_extension_names = features.extension_names();
 
-        unsafe {
+        // Optional extensions are enabled whenever they are supported
+        let has_extended_dynamic_state = unsafe {
             let extension_properties = instance
                 .enumerate_device_extension_properties(*physical_device)
                 .map_err(|err| {
@@ -122,8 +144,18 @@ where
                     return Err(DriverError::Unsupported);
                 }
             }
+
+            supported_extensions.contains(
+                vk::ExtExtendedDynamicStateFn::name()
+                    .to_string_lossy()
+                    .as_ref(),
+            )
         };
 
+        if has_extended_dynamic_state {
+            device_extension_names.push(vk::ExtExtendedDynamicStateFn::name().as_ptr());
+        }
+
         let priorities = [1.0];
         let queue = PhysicalDevice::queue_families(&physical_device).find(|qf| {
             qf.props.queue_flags.contains(
@@ -148,6 +180,9 @@ where
             vk::PhysicalDeviceImagelessFramebufferFeatures::builder();
         let mut buffer_device_address_features =
             vk::PhysicalDeviceBufferDeviceAddressFeatures::builder();
+        let mut multiview_features = vk::PhysicalDeviceMultiviewFeatures::builder();
+        let mut extended_dynamic_state_features =
+            vk::PhysicalDeviceExtendedDynamicStateFeaturesEXT::builder();
 
         #[cfg(not(target_os = "macos"))]
         let mut separate_depth_stencil_layouts_features =
@@ -165,10 +200,17 @@ where
             None
         };
 
+        let mut mesh_shader_features = if features.mesh_shading {
+            Some(vk::PhysicalDeviceMeshShaderFeaturesEXT::default())
+        } else {
+            None
+        };
+
         unsafe {
             let mut features2 = vk::PhysicalDeviceFeatures2::builder()
                 .push_next(&mut buffer_device_address_features)
-                .push_next(&mut imageless_framebuffer_features);
+                .push_next(&mut imageless_framebuffer_features)
+                .push_next(&mut multiview_features);
 
             #[cfg(not(target_os = "macos"))]
             {
@@ -181,6 +223,14 @@ where
                     .push_next(ray_tracing_pipeline_features.as_mut().unwrap());
             }
 
+            if features.mesh_shading {
+                features2 = features2.push_next(mesh_shader_features.as_mut().unwrap());
+            }
+
+            if has_extended_dynamic_state {
+                features2 = features2.push_next(&mut extended_dynamic_state_features);
+            }
+
             let mut features2 = features2.build();
 
             get_physical_device_features2(*physical_device, &mut features2);
@@ -283,10 +333,32 @@ where
                 }
             }
 
+            if features.mesh_shading {
+                let mesh_shader_features = mesh_shader_features.as_mut().unwrap();
+
+                if mesh_shader_features.mesh_shader != vk::TRUE {
+                    warn!("device does not support mesh shader");
+
+                    return Err(DriverError::Unsupported);
+                }
+
+                if mesh_shader_features.task_shader != vk::TRUE {
+                    warn!("device does not support task shader");
+
+                    return Err(DriverError::Unsupported);
+                }
+
+                // This feature requires VK_KHR_fragment_shading_rate, which we do not enable
+                mesh_shader_features.primitive_fragment_shading_rate_mesh_shader = vk::FALSE;
+            }
+
             let mut ray_tracing_pipeline_properties =
                 vk::PhysicalDeviceRayTracingPipelinePropertiesKHR::default();
 
-            let mut physical_properties = vk::PhysicalDeviceProperties2::builder();
+            let mut multiview_properties = vk::PhysicalDeviceMultiviewProperties::default();
+
+            let mut physical_properties =
+                vk::PhysicalDeviceProperties2::builder().push_next(&mut multiview_properties);
 
             if features.ray_tracing {
                 physical_properties =
@@ -302,6 +374,15 @@ where
                 None
             };
 
+            // Multiview is optional: render passes which use it fail to resolve without support
+            let multiview_properties = if multiview_features.multiview == vk::TRUE {
+                Some(multiview_properties.into())
+            } else {
+                info!("device does not support multiview");
+
+                None
+            };
+
             let device_create_info = vk::DeviceCreateInfo::builder()
                 .queue_create_infos(&queue_info)
                 .enabled_extension_names(&device_extension_names)
@@ -330,13 +411,16 @@ where
 
                 DriverError::Unsupported
             })?;
+            let pipeline_cache = Self::create_pipeline_cache(
+                &device,
+                &physical_device,
+                cfg.pipeline_cache_data.as_deref(),
+            )?;
             let queue = Queue {
                 queue: device.get_device_queue(queue.idx, 0),
                 family: queue,
             };
 
-            let immutable_samplers = Self::create_immutable_samplers(&device)?;
-
             let (surface_ext, swapchain_ext) = if cfg.presentation {
                 (
                     Some(khr::Surface::new(&instance.entry, &instance)),
@@ -355,85 +439,134 @@ where
                 (None, None)
             };
 
+            let extended_dynamic_state_ext = if has_extended_dynamic_state
+                && extended_dynamic_state_features.extended_dynamic_state == vk::TRUE
+            {
+                Some(ext::ExtendedDynamicState::new(&instance, &device))
+            } else {
+                info!("device does not support extended dynamic state");
+
+                None
+            };
+
+            let mesh_shader_ext = if cfg.mesh_shading {
+                Some(ext::MeshShader::new(&instance, &device))
+            } else {
+                None
+            };
+
             Ok(Self {
                 accel_struct_ext,
                 allocator: Some(Mutex::new(allocator)),
                 device,
-                immutable_samplers,
+                extended_dynamic_state_ext,
+                immutable_samplers: Default::default(),
                 instance,
+                mesh_shader_ext,
+                multiview_properties,
                 physical_device,
+                pipeline_cache,
                 queue,
                 ray_tracing_pipeline_ext,
                 ray_tracing_pipeline_properties,
                 surface_ext,
                 swapchain_ext,
+                workers: WorkerPool::new(),
             })
         }
     }
 
-    fn create_immutable_samplers(
+    /// Returns a sampler owned by the device which matches the given information, creating it if
+    /// required.
+    ///
+    /// These samplers are used as the immutable samplers of pipeline descriptor set layouts and
+    /// remain valid until the device is dropped.
+    pub fn immutable_sampler(this: &Self, info: SamplerInfo) -> Result<vk::Sampler, DriverError> {
+        let mut immutable_samplers = this.immutable_samplers.lock();
+
+        if let Some(sampler) = immutable_samplers.get(&info) {
+            return Ok(*sampler);
+        }
+
+        let sampler = unsafe { Sampler::create_vk(this, info) }?;
+        immutable_samplers.insert(info, sampler);
+
+        Ok(sampler)
+    }
+
+    /// Creates a pipeline cache seeded with previously saved data, if the data was created by the
+    /// same physical device and driver version.
+    unsafe fn create_pipeline_cache(
         device: &ash::Device,
-    ) -> Result<HashMap<SamplerDesc, vk::Sampler>, DriverError> {
-        let texel_filters = [vk::Filter::LINEAR, vk::Filter::NEAREST];
-        let mipmap_modes = [
-            vk::SamplerMipmapMode::LINEAR,
-            vk::SamplerMipmapMode::NEAREST,
-        ];
-        let address_modes = [
-            vk::SamplerAddressMode::CLAMP_TO_BORDER,
-            vk::SamplerAddressMode::CLAMP_TO_EDGE,
-            vk::SamplerAddressMode::MIRRORED_REPEAT,
-            vk::SamplerAddressMode::REPEAT,
-        ];
-
-        let mut res = HashMap::new();
-
-        for texel_filter in texel_filters {
-            for mipmap_mode in mipmap_modes {
-                for address_modes in address_modes {
-                    let anisotropy_enable = texel_filter == vk::Filter::LINEAR;
-
-                    res.insert(
-                        SamplerDesc {
-                            texel_filter,
-                            mipmap_mode,
-                            address_modes,
-                        },
-                        unsafe {
-                            let mut info = vk::SamplerCreateInfo::builder()
-                                .mag_filter(texel_filter)
-                                .min_filter(texel_filter)
-                                .mipmap_mode(mipmap_mode)
-                                .address_mode_u(address_modes)
-                                .address_mode_v(address_modes)
-                                .address_mode_w(address_modes)
-                                .max_lod(vk::LOD_CLAMP_NONE)
-                                .anisotropy_enable(anisotropy_enable);
-
-                            if anisotropy_enable {
-                                info = info.max_anisotropy(16.0);
-                            }
-
-                            device.create_sampler(&info, None)
-                        }
-                        .map_err(|err| {
-                            warn!("{err}");
-
-                            DriverError::Unsupported
-                        })?,
-                    );
-                }
+        physical_device: &PhysicalDevice,
+        data: Option<&[u8]>,
+    ) -> Result<vk::PipelineCache, DriverError> {
+        const HEADER_VERSION_ONE_LEN: usize = 32;
+
+        let data = data.filter(|data| {
+            let read_u32 = |offset: usize| {
+                data.get(offset..offset + 4)
+                    .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+            };
+            let header_len = read_u32(0).unwrap_or_default() as usize;
+            let header_version = read_u32(4);
+            let vendor_id = read_u32(8);
+            let device_id = read_u32(12);
+            let uuid = data.get(16..HEADER_VERSION_ONE_LEN);
+            let props = &physical_device.props;
+
+            if header_len < HEADER_VERSION_ONE_LEN
+                || header_len > data.len()
+                || header_version != Some(vk::PipelineCacheHeaderVersion::ONE.as_raw() as _)
+            {
+                info!("discarding invalid pipeline cache");
+
+                return false;
+            }
+
+            if vendor_id != Some(props.vendor_id)
+                || device_id != Some(props.device_id)
+                || uuid != Some(&props.pipeline_cache_uuid[..])
+            {
+                info!("discarding stale pipeline cache");
+
+                return false;
+            }
+
+            true
+        });
+
+        if let Some(data) = data {
+            match device.create_pipeline_cache(
+                &vk::PipelineCacheCreateInfo::builder().initial_data(data),
+                None,
+            ) {
+                Ok(pipeline_cache) => return Ok(pipeline_cache),
+                Err(err) => warn!("discarding pipeline cache: {err}"),
             }
         }
 
-        Ok(res)
+        device
+            .create_pipeline_cache(&vk::PipelineCacheCreateInfo::default(), None)
+            .map_err(|err| {
+                warn!("{err}");
+
+                DriverError::Unsupported
+            })
     }
 
-    pub fn immutable_sampler(this: &Self, info: SamplerDesc) -> vk::Sampler {
-        this.immutable_samplers
-            .get(&info)
-            .copied()
-            .unwrap_or_else(|| unimplemented!("{:?}", info))
+    /// Returns the data of the pipeline cache of this device, which may be saved and later used as
+    /// `DriverConfig::pipeline_cache_data` to avoid compiling the same pipelines again.
+    pub fn pipeline_cache_data(this: &Self) -> Result<Vec<u8>, DriverError> {
+        unsafe {
+            this.device
+                .get_pipeline_cache_data(this.pipeline_cache)
+                .map_err(|err| {
+                    warn!("{err}");
+
+                    DriverError::OutOfMemory
+                })
+        }
     }
 
     pub fn surface_formats(
@@ -524,6 +657,8 @@ where
 
         // trace!("drop");
 
+        self.workers.join();
+
         let res = unsafe { self.device.device_wait_idle() };
 
         if let Err(err) = res {
@@ -532,19 +667,22 @@ where
 
         self.allocator.take().unwrap();
 
-        for (_, sampler) in self.immutable_samplers.drain() {
+        for (_, sampler) in self.immutable_samplers.get_mut().drain() {
             unsafe {
                 self.device.destroy_sampler(sampler, None);
             }
         }
 
         unsafe {
+            self.device
+                .destroy_pipeline_cache(self.pipeline_cache, None);
             self.device.destroy_device(None);
         }
     }
 }
 
 pub struct FeatureFlags {
+    pub mesh_shading: bool,
     pub presentation: bool,
     pub ray_tracing: bool,
 }
@@ -564,6 +702,10 @@ impl FeatureFlags {
             ]);
         }
 
+        if self.mesh_shading {
+            res.push(vk::ExtMeshShaderFn::name());
+        }
+
         if self.presentation {
             res.push(khr::Swapchain::name());
         }
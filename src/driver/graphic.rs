@@ -1,7 +1,10 @@
 use {
     super::{
-        DescriptorBindingMap, Device, DriverError, PipelineDescriptorInfo, SampleCount, Shader,
-        SpecializationInfo,
+        render_pass::{create_render_pass, GraphicPipelineDesc},
+        worker::JobHandle,
+        BlockMember, DescriptorBindingMap, DescriptorBlockMap, DescriptorNameMap, Device,
+        DriverError, PipelineDescriptorInfo, RenderPassInfo, SampleCount, Shader,
+        SpecializationInfo, SpecializationValue,
     },
     crate::graph::AttachmentIndex,
     archery::{SharedPointer, SharedPointerKind},
@@ -9,66 +12,189 @@ use {
     derive_builder::Builder,
     log::{trace, warn},
     ordered_float::OrderedFloat,
-    std::{cmp::Ordering, collections::HashSet, ffi::CString, thread::panicking},
+    parking_lot::Mutex,
+    std::{
+        cmp::Ordering,
+        collections::{BTreeMap, HashMap, HashSet},
+        ffi::CString,
+        mem::size_of,
+        thread::panicking,
+    },
 };
 
-// TODO: Finally make this into a full struct and offer full features....
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-pub enum BlendMode {
-    Alpha,
-    PreMultipliedAlpha,
-    Replace,
+const COLOR_COMPONENT_RGBA: vk::ColorComponentFlags = vk::ColorComponentFlags::from_raw(0b1111);
+
+/// Specifies color blending and the color write mask of a color attachment.
+///
+/// Common modes are available as constants, such as `BlendMode::ALPHA` or `BlendMode::ADDITIVE`.
+#[derive(Builder, Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[builder(
+    build_fn(private, name = "fallible_build"),
+    derive(Debug),
+    pattern = "owned"
+)]
+pub struct BlendMode {
+    /// Operation used to combine the alpha components.
+    #[builder(default = "vk::BlendOp::ADD")]
+    pub alpha_blend_op: vk::BlendOp,
+
+    /// Enables blending; when disabled the fragment color is written as-is.
+    #[builder(default)]
+    pub blend_enable: bool,
+
+    /// Operation used to combine the color components.
+    #[builder(default = "vk::BlendOp::ADD")]
+    pub color_blend_op: vk::BlendOp,
+
+    /// Components which are written to the attachment, whether or not blending is enabled.
+    #[builder(default = "COLOR_COMPONENT_RGBA")]
+    pub color_write_mask: vk::ColorComponentFlags,
+
+    /// Factor applied to the destination alpha component.
+    #[builder(default = "vk::BlendFactor::ZERO")]
+    pub dst_alpha_blend_factor: vk::BlendFactor,
+
+    /// Factor applied to the destination color components.
+    #[builder(default = "vk::BlendFactor::ZERO")]
+    pub dst_color_blend_factor: vk::BlendFactor,
+
+    /// Factor applied to the source alpha component.
+    #[builder(default = "vk::BlendFactor::ONE")]
+    pub src_alpha_blend_factor: vk::BlendFactor,
+
+    /// Factor applied to the source color components.
+    #[builder(default = "vk::BlendFactor::ONE")]
+    pub src_color_blend_factor: vk::BlendFactor,
 }
 
 impl BlendMode {
+    /// Adds the source color to the destination color.
+    pub const ADDITIVE: Self = Self {
+        alpha_blend_op: vk::BlendOp::ADD,
+        blend_enable: true,
+        color_blend_op: vk::BlendOp::ADD,
+        color_write_mask: COLOR_COMPONENT_RGBA,
+        dst_alpha_blend_factor: vk::BlendFactor::ONE,
+        dst_color_blend_factor: vk::BlendFactor::ONE,
+        src_alpha_blend_factor: vk::BlendFactor::ONE,
+        src_color_blend_factor: vk::BlendFactor::ONE,
+    };
+
+    /// Blends the source color over the destination color using source alpha.
+    pub const ALPHA: Self = Self {
+        alpha_blend_op: vk::BlendOp::ADD,
+        blend_enable: true,
+        color_blend_op: vk::BlendOp::ADD,
+        color_write_mask: COLOR_COMPONENT_RGBA,
+        dst_alpha_blend_factor: vk::BlendFactor::ONE_MINUS_SRC_ALPHA,
+        dst_color_blend_factor: vk::BlendFactor::ONE_MINUS_SRC_ALPHA,
+        src_alpha_blend_factor: vk::BlendFactor::SRC_ALPHA,
+        src_color_blend_factor: vk::BlendFactor::SRC_ALPHA,
+    };
+
+    /// Keeps the component-wise maximum of the source and destination colors.
+    pub const MAX: Self = Self {
+        alpha_blend_op: vk::BlendOp::MAX,
+        blend_enable: true,
+        color_blend_op: vk::BlendOp::MAX,
+        color_write_mask: COLOR_COMPONENT_RGBA,
+        dst_alpha_blend_factor: vk::BlendFactor::ONE,
+        dst_color_blend_factor: vk::BlendFactor::ONE,
+        src_alpha_blend_factor: vk::BlendFactor::ONE,
+        src_color_blend_factor: vk::BlendFactor::ONE,
+    };
+
+    /// Keeps the component-wise minimum of the source and destination colors.
+    pub const MIN: Self = Self {
+        alpha_blend_op: vk::BlendOp::MIN,
+        blend_enable: true,
+        color_blend_op: vk::BlendOp::MIN,
+        color_write_mask: COLOR_COMPONENT_RGBA,
+        dst_alpha_blend_factor: vk::BlendFactor::ONE,
+        dst_color_blend_factor: vk::BlendFactor::ONE,
+        src_alpha_blend_factor: vk::BlendFactor::ONE,
+        src_color_blend_factor: vk::BlendFactor::ONE,
+    };
+
+    /// Multiplies the source color with the destination color.
+    pub const MULTIPLY: Self = Self {
+        alpha_blend_op: vk::BlendOp::ADD,
+        blend_enable: true,
+        color_blend_op: vk::BlendOp::ADD,
+        color_write_mask: COLOR_COMPONENT_RGBA,
+        dst_alpha_blend_factor: vk::BlendFactor::ZERO,
+        dst_color_blend_factor: vk::BlendFactor::ZERO,
+        src_alpha_blend_factor: vk::BlendFactor::DST_ALPHA,
+        src_color_blend_factor: vk::BlendFactor::DST_COLOR,
+    };
+
+    /// Blends the source color over the destination color using source alpha, while accumulating
+    /// alpha.
+    pub const PRE_MULTIPLIED_ALPHA: Self = Self {
+        alpha_blend_op: vk::BlendOp::ADD,
+        blend_enable: true,
+        color_blend_op: vk::BlendOp::ADD,
+        color_write_mask: COLOR_COMPONENT_RGBA,
+        dst_alpha_blend_factor: vk::BlendFactor::ONE,
+        dst_color_blend_factor: vk::BlendFactor::ONE_MINUS_SRC_ALPHA,
+        src_alpha_blend_factor: vk::BlendFactor::ONE,
+        src_color_blend_factor: vk::BlendFactor::SRC_ALPHA,
+    };
+
+    /// Writes the source color, replacing the destination color.
+    pub const REPLACE: Self = Self {
+        alpha_blend_op: vk::BlendOp::ADD,
+        blend_enable: false,
+        color_blend_op: vk::BlendOp::ADD,
+        color_write_mask: COLOR_COMPONENT_RGBA,
+        dst_alpha_blend_factor: vk::BlendFactor::ZERO,
+        dst_color_blend_factor: vk::BlendFactor::ZERO,
+        src_alpha_blend_factor: vk::BlendFactor::ONE,
+        src_color_blend_factor: vk::BlendFactor::ONE,
+    };
+
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> BlendModeBuilder {
+        BlendModeBuilder::default()
+    }
+
+    /// Returns a copy of this mode which only writes the given color components.
+    pub fn with_write_mask(mut self, color_write_mask: vk::ColorComponentFlags) -> Self {
+        self.color_write_mask = color_write_mask;
+        self
+    }
+
     pub fn into_vk(&self) -> vk::PipelineColorBlendAttachmentState {
-        match self {
-            Self::Alpha => vk::PipelineColorBlendAttachmentState {
-                blend_enable: 1,
-                src_color_blend_factor: vk::BlendFactor::SRC_ALPHA,
-                dst_color_blend_factor: vk::BlendFactor::ONE_MINUS_SRC_ALPHA,
-                color_blend_op: vk::BlendOp::ADD,
-                src_alpha_blend_factor: vk::BlendFactor::SRC_ALPHA,
-                dst_alpha_blend_factor: vk::BlendFactor::ONE_MINUS_SRC_ALPHA,
-                alpha_blend_op: vk::BlendOp::ADD,
-                color_write_mask: vk::ColorComponentFlags::R
-                    | vk::ColorComponentFlags::G
-                    | vk::ColorComponentFlags::B
-                    | vk::ColorComponentFlags::A,
-            },
-            Self::PreMultipliedAlpha => vk::PipelineColorBlendAttachmentState {
-                blend_enable: vk::TRUE,
-                src_color_blend_factor: vk::BlendFactor::SRC_ALPHA,
-                dst_color_blend_factor: vk::BlendFactor::ONE_MINUS_SRC_ALPHA,
-                color_blend_op: vk::BlendOp::ADD,
-                src_alpha_blend_factor: vk::BlendFactor::ONE,
-                dst_alpha_blend_factor: vk::BlendFactor::ONE,
-                alpha_blend_op: vk::BlendOp::ADD,
-                color_write_mask: vk::ColorComponentFlags::R
-                    | vk::ColorComponentFlags::G
-                    | vk::ColorComponentFlags::B
-                    | vk::ColorComponentFlags::A,
-            },
-            Self::Replace => vk::PipelineColorBlendAttachmentState {
-                blend_enable: 0,
-                src_color_blend_factor: vk::BlendFactor::SRC_COLOR,
-                dst_color_blend_factor: vk::BlendFactor::ONE_MINUS_DST_COLOR,
-                color_blend_op: vk::BlendOp::ADD,
-                src_alpha_blend_factor: vk::BlendFactor::ZERO,
-                dst_alpha_blend_factor: vk::BlendFactor::ZERO,
-                alpha_blend_op: vk::BlendOp::ADD,
-                color_write_mask: vk::ColorComponentFlags::R
-                    | vk::ColorComponentFlags::G
-                    | vk::ColorComponentFlags::B
-                    | vk::ColorComponentFlags::A,
-            },
+        vk::PipelineColorBlendAttachmentState {
+            blend_enable: self.blend_enable as _,
+            src_color_blend_factor: self.src_color_blend_factor,
+            dst_color_blend_factor: self.dst_color_blend_factor,
+            color_blend_op: self.color_blend_op,
+            src_alpha_blend_factor: self.src_alpha_blend_factor,
+            dst_alpha_blend_factor: self.dst_alpha_blend_factor,
+            alpha_blend_op: self.alpha_blend_op,
+            color_write_mask: self.color_write_mask,
         }
     }
 }
 
+// HACK: https://github.com/colin-kiegel/rust-derive-builder/issues/56
+impl BlendModeBuilder {
+    pub fn build(self) -> BlendMode {
+        self.fallible_build()
+            .expect("All required fields set at initialization")
+    }
+}
+
 impl Default for BlendMode {
     fn default() -> Self {
-        Self::Replace
+        Self::REPLACE
+    }
+}
+
+impl From<BlendModeBuilder> for BlendMode {
+    fn from(mode: BlendModeBuilder) -> Self {
+        mode.build()
     }
 }
 
@@ -96,7 +222,7 @@ impl DepthStencilMode {
         DepthStencilModeBuilder::default()
     }
 
-    pub(super) fn into_vk(self) -> vk::PipelineDepthStencilStateCreateInfo {
+    pub fn into_vk(self) -> vk::PipelineDepthStencilStateCreateInfo {
         vk::PipelineDepthStencilStateCreateInfo {
             back: self.back.into_vk(),
             depth_bounds_test_enable: self.bounds_test as _,
@@ -162,11 +288,15 @@ where
     P: SharedPointerKind,
 {
     pub descriptor_bindings: DescriptorBindingMap,
+    pub descriptor_blocks: DescriptorBlockMap,
     pub descriptor_info: PipelineDescriptorInfo<P>,
+    pub descriptor_names: DescriptorNameMap,
     device: SharedPointer<Device<P>, P>,
     pub info: GraphicPipelineInfo,
     pub input_attachments: HashSet<AttachmentIndex>,
     pub layout: vk::PipelineLayout,
+    prewarm_jobs: Mutex<Vec<JobHandle<()>>>,
+    pub push_constant_block: Vec<BlockMember>,
     pub push_constants: Vec<vk::PushConstantRange>,
     shader_modules: Vec<vk::ShaderModule>,
     stage_flags: vk::ShaderStageFlags,
@@ -195,25 +325,45 @@ where
             .map(|shader| shader.into())
             .collect::<Vec<Shader>>();
 
-        let vertex_input = shaders
+        // Mesh pipelines have no vertex input; primitives are generated by the mesh stage
+        let has_mesh_stage = shaders
+            .iter()
+            .any(|shader| shader.stage.contains(vk::ShaderStageFlags::MESH_EXT));
+
+        if has_mesh_stage && device.mesh_shader_ext.is_none() {
+            warn!("mesh shading is not enabled");
+
+            return Err(DriverError::Unsupported);
+        }
+
+        let vertex_shader = shaders
             .iter()
-            .find(|shader| shader.stage == vk::ShaderStageFlags::VERTEX)
-            .expect("vertex shader not found")
-            .vertex_input();
+            .find(|shader| shader.stage == vk::ShaderStageFlags::VERTEX);
+        let vertex_input = if let Some(vertex_shader) = vertex_shader {
+            vertex_shader.validate_vertex_layout()?;
+            vertex_shader.vertex_input()?
+        } else {
+            Default::default()
+        };
 
         // Check for proper stages because vulkan may not complain but this is bad
+        let has_vertex_stage = shaders
+            .iter()
+            .any(|shader| shader.stage.contains(vk::ShaderStageFlags::VERTEX));
         let has_fragment_stage = shaders
             .iter()
             .any(|shader| shader.stage.contains(vk::ShaderStageFlags::FRAGMENT));
-        let has_tesselation_stage = shaders.iter().any(|shader| {
+        let has_tesselation_ctrl_stage = shaders.iter().any(|shader| {
             shader
                 .stage
                 .contains(vk::ShaderStageFlags::TESSELLATION_CONTROL)
-        }) && shaders.iter().any(|shader| {
+        });
+        let has_tesselation_eval_stage = shaders.iter().any(|shader| {
             shader
                 .stage
                 .contains(vk::ShaderStageFlags::TESSELLATION_EVALUATION)
         });
+        let has_tesselation_stage = has_tesselation_ctrl_stage && has_tesselation_eval_stage;
         let has_geometry_stage = shaders
             .iter()
             .any(|shader| shader.stage.contains(vk::ShaderStageFlags::GEOMETRY));
@@ -222,19 +372,134 @@ where
             has_fragment_stage || has_tesselation_stage || has_geometry_stage,
             "invalid shader stage combination"
         );
+        debug_assert!(
+            has_vertex_stage != has_mesh_stage,
+            "vertex or mesh shader (but not both) required"
+        );
+        debug_assert!(
+            !has_mesh_stage || !(has_tesselation_stage || has_geometry_stage),
+            "mesh shader cannot be combined with tesselation or geometry shaders"
+        );
+
+        if info.topology == vk::PrimitiveTopology::PATCH_LIST && !has_tesselation_stage {
+            warn!("patch list topology requires tesselation shaders");
+
+            return Err(DriverError::InvalidData);
+        }
+
+        if info.primitive_restart
+            && matches!(
+                info.topology,
+                vk::PrimitiveTopology::POINT_LIST
+                    | vk::PrimitiveTopology::LINE_LIST
+                    | vk::PrimitiveTopology::TRIANGLE_LIST
+                    | vk::PrimitiveTopology::LINE_LIST_WITH_ADJACENCY
+                    | vk::PrimitiveTopology::TRIANGLE_LIST_WITH_ADJACENCY
+                    | vk::PrimitiveTopology::PATCH_LIST
+            )
+        {
+            warn!("primitive restart is not supported by {:?}", info.topology);
+
+            return Err(DriverError::InvalidData);
+        }
+
+        if let Some(line_width) = info.line_width {
+            if *line_width != 1.0 && device.physical_device.features.wide_lines != vk::TRUE {
+                warn!("device does not support wide lines");
+
+                return Err(DriverError::Unsupported);
+            }
+
+            let [min_line_width, max_line_width] =
+                device.physical_device.props.limits.line_width_range;
+            if *line_width < min_line_width || *line_width > max_line_width {
+                warn!(
+                    "invalid line width: {line_width} (range {min_line_width}..={max_line_width})"
+                );
+
+                return Err(DriverError::InvalidData);
+            }
+        }
+
+        if info.polygon_mode != vk::PolygonMode::FILL
+            && device.physical_device.features.fill_mode_non_solid != vk::TRUE
+        {
+            warn!("device does not support non-solid fill modes");
+
+            return Err(DriverError::Unsupported);
+        }
+
+        if info.logic_op.is_some() && device.physical_device.features.logic_op != vk::TRUE {
+            warn!("device does not support logic ops");
+
+            return Err(DriverError::Unsupported);
+        }
+
+        if info
+            .attachment_blends
+            .values()
+            .any(|blend| *blend != info.blend)
+            && device.physical_device.features.independent_blend != vk::TRUE
+        {
+            warn!("device does not support independent blend");
+
+            return Err(DriverError::Unsupported);
+        }
+
+        if has_tesselation_ctrl_stage != has_tesselation_eval_stage {
+            warn!("tesselation control and evaluation shaders must be used together");
+
+            return Err(DriverError::InvalidData);
+        }
+
+        if has_tesselation_stage {
+            if device.physical_device.features.tessellation_shader != vk::TRUE {
+                warn!("device does not support tesselation shaders");
+
+                return Err(DriverError::Unsupported);
+            }
+
+            let max_patch_size = device
+                .physical_device
+                .props
+                .limits
+                .max_tessellation_patch_size;
+            if info.patch_control_points == 0 || info.patch_control_points > max_patch_size {
+                warn!(
+                    "invalid patch control points: {} (max {max_patch_size})",
+                    info.patch_control_points
+                );
+
+                return Err(DriverError::InvalidData);
+            }
+        }
 
         let descriptor_bindings = shaders
             .iter()
             .map(|shader| shader.descriptor_bindings(&device))
-            .collect::<Vec<_>>();
+            .collect::<Result<Vec<_>, _>>()?;
         let descriptor_bindings = Shader::merge_descriptor_bindings(descriptor_bindings);
+        let descriptor_blocks = shaders
+            .iter()
+            .flat_map(|shader| shader.descriptor_blocks())
+            .collect();
         let descriptor_info = PipelineDescriptorInfo::create(&device, &descriptor_bindings)?;
+        let descriptor_names =
+            Shader::merge_descriptor_names(shaders.iter().map(|shader| shader.descriptor_names()));
         let descriptor_sets_layouts = descriptor_info
             .layouts
             .iter()
             .map(|(_, descriptor_set_layout)| **descriptor_set_layout)
             .collect::<Box<[_]>>();
 
+        // Stages which share the push constant block each report its members
+        let mut push_constant_block = shaders
+            .iter()
+            .flat_map(|shader| shader.push_constant_block())
+            .collect::<Vec<_>>();
+        push_constant_block.sort_by_key(|member| member.offset);
+        push_constant_block.dedup_by(|lhs, rhs| lhs.offset == rhs.offset && lhs.name == rhs.name);
+
         let mut push_constants = shaders
             .iter()
             .map(|shader| shader.push_constant_range())
@@ -377,11 +642,15 @@ where
 
             Ok(Self {
                 descriptor_bindings,
+                descriptor_blocks,
                 descriptor_info,
+                descriptor_names,
                 device,
                 info,
                 input_attachments,
                 layout,
+                prewarm_jobs: Default::default(),
+                push_constant_block,
                 push_constants,
                 shader_modules,
                 stage_flags,
@@ -397,6 +666,50 @@ where
         }
     }
 
+    /// Begins creating this pipeline on a worker thread for a subpass of the given render pass, so
+    /// that it is quickly created from the device pipeline cache when first used with a compatible
+    /// render pass.
+    ///
+    /// Render passes are compatible when their attachments have the same formats and sample counts
+    /// and their subpasses use the same attachments; load and store operations and image layouts
+    /// may differ.
+    pub fn prewarm(
+        &self,
+        render_pass: &RenderPassInfo,
+        subpass_idx: u32,
+        depth_stencil: Option<DepthStencilMode>,
+    ) -> Result<(), DriverError> {
+        if subpass_idx as usize >= render_pass.subpasses.len() {
+            warn!("invalid subpass index {subpass_idx}");
+
+            return Err(DriverError::InvalidData);
+        }
+
+        let vk_render_pass = create_render_pass(&self.device, render_pass)?;
+        let desc = GraphicPipelineDesc::new(
+            &self.device,
+            self,
+            vk_render_pass,
+            render_pass,
+            subpass_idx,
+            depth_stencil,
+        );
+        let device = (**self.device).clone();
+        let pipeline_cache = self.device.pipeline_cache;
+
+        let mut jobs = self.prewarm_jobs.lock();
+        jobs.retain(|job| !job.is_finished());
+        jobs.push(self.device.workers.submit(move || unsafe {
+            if let Ok(pipeline) = desc.create(&device, pipeline_cache) {
+                device.destroy_pipeline(pipeline, None);
+            }
+
+            device.destroy_render_pass(vk_render_pass, None);
+        }));
+
+        Ok(())
+    }
+
     pub fn stages(&self) -> vk::ShaderStageFlags {
         self.stage_flags
     }
@@ -411,6 +724,11 @@ where
             return;
         }
 
+        // Worker threads use the layout and shader modules
+        for job in self.prewarm_jobs.get_mut().drain(..) {
+            job.wait();
+        }
+
         unsafe {
             self.device.destroy_pipeline_layout(self.layout, None);
         }
@@ -430,28 +748,96 @@ where
     pattern = "owned"
 )]
 pub struct GraphicPipelineInfo {
+    /// Blend modes of specific color attachments, overriding `blend`.
+    ///
+    /// _NOTE:_ Requires the `independentBlend` device feature if any mode differs from `blend`.
+    #[builder(default, setter(custom))]
+    pub attachment_blends: BTreeMap<AttachmentIndex, BlendMode>,
+
+    /// Creates this pipeline on a worker thread the first time it is used with each render pass,
+    /// instead of blocking while it is created.
+    ///
+    /// Executions of a pass which use this pipeline are skipped (nothing is drawn) until the
+    /// pipeline is ready.
+    #[builder(default)]
+    pub background_compile: bool,
+
+    /// Blend mode of all color attachments which are not specified by `attachment_blends`.
     #[builder(default)]
     pub blend: BlendMode,
 
+    /// Constant color used by the `CONSTANT_*` blend factors.
+    ///
+    /// If `None` the blend constants are dynamic and should be set using
+    /// `Draw::set_blend_constants`.
+    #[builder(default = "Some([OrderedFloat(0.0); 4])")]
+    pub blend_constants: Option<[OrderedFloat<f32>; 4]>,
+
     #[builder(default = "vk::CullModeFlags::BACK")]
     pub cull_mode: vk::CullModeFlags,
 
     #[builder(default, setter(strip_option))]
     pub depth_stencil: Option<DepthStencilMode>,
 
+    /// Makes depth bias, depth bounds, and stencil masks and reference values dynamic state, which
+    /// may be set using `Draw`.
+    ///
+    /// When `VK_EXT_extended_dynamic_state` is supported cull mode, front face, and depth compare,
+    /// test, and write state are also dynamic.
+    ///
+    /// Dynamic state is set to the static values of this pipeline each time it is bound.
+    #[builder(default)]
+    pub dynamic_state: bool,
+
     #[builder(default = "vk::FrontFace::COUNTER_CLOCKWISE")]
     pub front_face: vk::FrontFace,
 
+    /// Width of rasterized lines.
+    ///
+    /// If `None` the line width is dynamic and should be set using `Draw::set_line_width`.
+    ///
+    /// _NOTE:_ Widths other than `1.0` require the `wideLines` device feature.
+    #[builder(default = "Some(OrderedFloat(1.0))")]
+    pub line_width: Option<OrderedFloat<f32>>,
+
+    /// Bitwise operation applied to all color attachments instead of blending.
+    ///
+    /// _NOTE:_ Requires the `logicOp` device feature.
+    #[builder(default, setter(strip_option))]
+    pub logic_op: Option<vk::LogicOp>,
+
     /// A descriptive name used in debugging messages.
     #[builder(default, setter(strip_option))]
     pub name: Option<String>,
 
+    /// Number of control points per patch; only used with tesselation shaders.
+    #[builder(default = "3")]
+    pub patch_control_points: u32,
+
+    /// _NOTE:_ Modes other than `FILL` require the `fillModeNonSolid` device feature.
     #[builder(default = "vk::PolygonMode::FILL")]
     pub polygon_mode: vk::PolygonMode,
 
+    /// Enables a special index value (`0xFFFF` or `0xFFFFFFFF`) which restarts strip and fan
+    /// topologies during indexed draws.
+    ///
+    /// _NOTE:_ Not supported by list topologies.
+    #[builder(default)]
+    pub primitive_restart: bool,
+
     #[builder(default = "SampleCount::X1")]
     pub samples: SampleCount,
 
+    /// Origin of the tesselation domain space; only used with tesselation shaders.
+    #[builder(default = "vk::TessellationDomainOrigin::UPPER_LEFT")]
+    pub tessellation_domain_origin: vk::TessellationDomainOrigin,
+
+    /// Primitive topology of vertex input.
+    ///
+    /// _NOTE:_ Pipelines with tesselation shaders always use `PATCH_LIST`.
+    #[builder(default = "vk::PrimitiveTopology::TRIANGLE_LIST")]
+    pub topology: vk::PrimitiveTopology,
+
     #[builder(default)]
     pub two_sided: bool,
 }
@@ -465,6 +851,14 @@ impl GraphicPipelineInfo {
 
 // HACK: https://github.com/colin-kiegel/rust-derive-builder/issues/56
 impl GraphicPipelineInfoBuilder {
+    /// Sets the blend mode of a specific color attachment.
+    pub fn attachment_blend(mut self, attachment: AttachmentIndex, blend: BlendMode) -> Self {
+        self.attachment_blends
+            .get_or_insert_with(Default::default)
+            .insert(attachment, blend);
+        self
+    }
+
     pub fn build(self) -> GraphicPipelineInfo {
         self.fallible_build()
             .expect("All required fields set at initialization")
@@ -474,13 +868,23 @@ impl GraphicPipelineInfoBuilder {
 impl Default for GraphicPipelineInfo {
     fn default() -> Self {
         Self {
+            attachment_blends: Default::default(),
+            background_compile: false,
             blend: BlendMode::default(),
+            blend_constants: Some([OrderedFloat(0.0); 4]),
             cull_mode: vk::CullModeFlags::BACK,
             depth_stencil: None,
+            dynamic_state: false,
             front_face: vk::FrontFace::COUNTER_CLOCKWISE,
+            line_width: Some(OrderedFloat(1.0)),
+            logic_op: None,
             name: None,
+            patch_control_points: 3,
             polygon_mode: vk::PolygonMode::FILL,
+            primitive_restart: false,
             samples: SampleCount::X1,
+            tessellation_domain_origin: vk::TessellationDomainOrigin::UPPER_LEFT,
+            topology: vk::PrimitiveTopology::TRIANGLE_LIST,
             two_sided: false,
         }
     }
@@ -501,6 +905,97 @@ pub struct GraphicPipelineState {
     pub vertex_input: VertexInputState,
 }
 
+/// Graphic pipelines created from the same shader code using different specialization constant
+/// values.
+///
+/// Each variant is created the first time it is requested and cached for later requests.
+#[derive(Debug)]
+pub struct GraphicPipelineVariants<P>
+where
+    P: SharedPointerKind,
+{
+    device: SharedPointer<Device<P>, P>,
+    info: GraphicPipelineInfo,
+    pipelines: HashMap<Vec<SpecializationInfo>, SharedPointer<GraphicPipeline<P>, P>>,
+    shaders: Vec<Shader>,
+}
+
+impl<P> GraphicPipelineVariants<P>
+where
+    P: SharedPointerKind,
+{
+    pub fn new<S>(
+        device: &SharedPointer<Device<P>, P>,
+        info: impl Into<GraphicPipelineInfo>,
+        shaders: impl IntoIterator<Item = S>,
+    ) -> Self
+    where
+        S: Into<Shader>,
+    {
+        Self {
+            device: SharedPointer::clone(device),
+            info: info.into(),
+            pipelines: Default::default(),
+            shaders: shaders.into_iter().map(|shader| shader.into()).collect(),
+        }
+    }
+
+    /// Returns the pipeline variant which uses the given named specialization constant values, in
+    /// addition to the specialization info of each shader.
+    ///
+    /// Each constant is set in every shader which declares a constant of the same name.
+    pub fn pipeline<'a>(
+        &mut self,
+        constants: impl IntoIterator<Item = (&'a str, SpecializationValue)>,
+    ) -> Result<SharedPointer<GraphicPipeline<P>, P>, DriverError> {
+        let constants = constants.into_iter().collect::<Vec<_>>();
+        let mut specialization_infos = Vec::with_capacity(self.shaders.len());
+        for shader in &self.shaders {
+            let spec_constants = shader.specialization_constants();
+            let mut specialization_info = shader.specialization_info.clone().unwrap_or_default();
+            for (name, value) in &constants {
+                if spec_constants.contains_key(*name) {
+                    specialization_info.set_named(&spec_constants, name, *value)?;
+                }
+            }
+
+            specialization_infos.push(specialization_info);
+        }
+
+        for (name, _) in &constants {
+            if !self
+                .shaders
+                .iter()
+                .any(|shader| shader.specialization_constants().contains_key(*name))
+            {
+                warn!("specialization constant `{name}` not found");
+
+                return Err(DriverError::InvalidData);
+            }
+        }
+
+        if let Some(pipeline) = self.pipelines.get(&specialization_infos) {
+            return Ok(SharedPointer::clone(pipeline));
+        }
+
+        let shaders = self
+            .shaders
+            .iter()
+            .zip(specialization_infos.iter().cloned())
+            .map(|(shader, specialization_info)| shader.specialized(specialization_info))
+            .collect::<Result<Vec<_>, _>>()?;
+        let pipeline = SharedPointer::new(GraphicPipeline::create(
+            &self.device,
+            self.info.clone(),
+            shaders,
+        )?);
+        self.pipelines
+            .insert(specialization_infos, SharedPointer::clone(&pipeline));
+
+        Ok(pipeline)
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct MultisampleState {
     pub alpha_to_coverage_enable: bool,
@@ -517,7 +1012,7 @@ pub struct RasterizationState {
     pub two_sided: bool,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Stage {
     pub flags: vk::ShaderStageFlags,
     pub module: vk::ShaderModule,
@@ -550,8 +1045,137 @@ impl Default for StencilMode {
     }
 }
 
-#[derive(Debug, Default)]
+/// Vertex buffer bindings and attributes used by a graphic pipeline.
+///
+/// Set on a vertex shader using [`Shader::vertex_layout`] to replace the layout reflected from the
+/// shader code.
+#[derive(Clone, Debug, Default)]
 pub struct VertexInputState {
     pub vertex_binding_descriptions: Vec<vk::VertexInputBindingDescription>,
     pub vertex_attribute_descriptions: Vec<vk::VertexInputAttributeDescription>,
 }
+
+impl VertexInputState {
+    /// Adds a binding which uses the stride and attributes of the given vertex type.
+    ///
+    /// Use this to combine per-vertex and per-instance data:
+    ///
+    /// ```ignore
+    /// let vertex_layout = VertexInputState::default()
+    ///     .vertex_binding::<MeshVertex>(0, vk::VertexInputRate::VERTEX)
+    ///     .vertex_binding::<MeshInstance>(1, vk::VertexInputRate::INSTANCE);
+    /// ```
+    pub fn vertex_binding<V>(mut self, binding: u32, input_rate: vk::VertexInputRate) -> Self
+    where
+        V: Vertex,
+    {
+        self.vertex_binding_descriptions
+            .push(vk::VertexInputBindingDescription {
+                binding,
+                stride: size_of::<V>() as _,
+                input_rate,
+            });
+        self.vertex_attribute_descriptions
+            .extend(V::vertex_attributes(binding));
+        self
+    }
+}
+
+/// A `#[repr(C)]` type which is read from a vertex buffer.
+///
+/// Usually implemented using `#[derive(Vertex)]` from the `screen-13-derive` crate.
+pub trait Vertex: Sized {
+    /// Returns the attributes of this type when read from the given vertex buffer binding.
+    fn vertex_attributes(binding: u32) -> Vec<vk::VertexInputAttributeDescription>;
+
+    /// Returns a layout containing only this type at binding zero, read per-vertex.
+    fn vertex_input() -> VertexInputState {
+        VertexInputState::default().vertex_binding::<Self>(0, vk::VertexInputRate::VERTEX)
+    }
+}
+
+/// A field type of a [`Vertex`] with a known attribute format.
+///
+/// Fields of other types, or which use packed or normalized formats such as `R8G8B8A8_UNORM`,
+/// must specify a format explicitly.
+pub trait VertexFormat {
+    /// The number of matrix columns of this type, each of which is read from the next location
+    /// using [`Self::FORMAT`].
+    const COLUMNS: u32 = 1;
+
+    const FORMAT: vk::Format;
+}
+
+macro_rules! vertex_format {
+    ($($ty:ty => $fmt:ident),* $(,)?) => {
+        $(
+            impl VertexFormat for $ty {
+                const FORMAT: vk::Format = vk::Format::$fmt;
+            }
+        )*
+    };
+}
+
+vertex_format!(
+    f32 => R32_SFLOAT,
+    [f32; 1] => R32_SFLOAT,
+    [f32; 2] => R32G32_SFLOAT,
+    [f32; 3] => R32G32B32_SFLOAT,
+    [f32; 4] => R32G32B32A32_SFLOAT,
+    f64 => R64_SFLOAT,
+    [f64; 1] => R64_SFLOAT,
+    [f64; 2] => R64G64_SFLOAT,
+    i8 => R8_SINT,
+    [i8; 1] => R8_SINT,
+    [i8; 2] => R8G8_SINT,
+    [i8; 3] => R8G8B8_SINT,
+    [i8; 4] => R8G8B8A8_SINT,
+    i16 => R16_SINT,
+    [i16; 1] => R16_SINT,
+    [i16; 2] => R16G16_SINT,
+    [i16; 3] => R16G16B16_SINT,
+    [i16; 4] => R16G16B16A16_SINT,
+    i32 => R32_SINT,
+    [i32; 1] => R32_SINT,
+    [i32; 2] => R32G32_SINT,
+    [i32; 3] => R32G32B32_SINT,
+    [i32; 4] => R32G32B32A32_SINT,
+    u8 => R8_UINT,
+    [u8; 1] => R8_UINT,
+    [u8; 2] => R8G8_UINT,
+    [u8; 3] => R8G8B8_UINT,
+    [u8; 4] => R8G8B8A8_UINT,
+    u16 => R16_UINT,
+    [u16; 1] => R16_UINT,
+    [u16; 2] => R16G16_UINT,
+    [u16; 3] => R16G16B16_UINT,
+    [u16; 4] => R16G16B16A16_UINT,
+    u32 => R32_UINT,
+    [u32; 1] => R32_UINT,
+    [u32; 2] => R32G32_UINT,
+    [u32; 3] => R32G32B32_UINT,
+    [u32; 4] => R32G32B32A32_UINT,
+);
+
+macro_rules! vertex_format_matrix {
+    ($([[f32; $rows:literal]; $columns:literal] => $fmt:ident),* $(,)?) => {
+        $(
+            impl VertexFormat for [[f32; $rows]; $columns] {
+                const COLUMNS: u32 = $columns;
+                const FORMAT: vk::Format = vk::Format::$fmt;
+            }
+        )*
+    };
+}
+
+vertex_format_matrix!(
+    [[f32; 2]; 2] => R32G32_SFLOAT,
+    [[f32; 2]; 3] => R32G32_SFLOAT,
+    [[f32; 2]; 4] => R32G32_SFLOAT,
+    [[f32; 3]; 2] => R32G32B32_SFLOAT,
+    [[f32; 3]; 3] => R32G32B32_SFLOAT,
+    [[f32; 3]; 4] => R32G32B32_SFLOAT,
+    [[f32; 4]; 2] => R32G32B32A32_SFLOAT,
+    [[f32; 4]; 3] => R32G32B32A32_SFLOAT,
+    [[f32; 4]; 4] => R32G32B32A32_SFLOAT,
+);
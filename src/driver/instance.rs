@@ -114,7 +114,7 @@ impl Instance {
         let instance_extensions = required_extensions
             .iter()
             .map(|ext| ext.as_ptr())
-            .chain(unsafe { Self::extension_names(debug).into_iter() })
+            .chain(unsafe { Self::extension_names(&entry, debug).into_iter() })
             .collect::<Box<[_]>>();
         let layer_names = Self::layer_names(debug);
         let layer_names: Vec<*const i8> = layer_names
@@ -184,9 +184,20 @@ impl Instance {
         })
     }
 
-    unsafe fn extension_names(debug: bool) -> Vec<*const i8> {
+    unsafe fn extension_names(entry: &Entry, debug: bool) -> Vec<*const i8> {
         let mut res = vec![];
 
+        // Optional: allows HDR and extended color spaces of swapchain surfaces
+        let swapchain_colorspace = vk::ExtSwapchainColorspaceFn::name();
+        if entry
+            .enumerate_instance_extension_properties(None)
+            .unwrap_or_default()
+            .iter()
+            .any(|ext| CStr::from_ptr(ext.extension_name.as_ptr()) == swapchain_colorspace)
+        {
+            res.push(swapchain_colorspace.as_ptr());
+        }
+
         if debug {
             #[allow(deprecated)]
             res.push(ext::DebugReport::name().as_ptr());
@@ -241,8 +252,9 @@ impl Instance {
                         })
                         .collect();
                     let mem_props = this.get_physical_device_memory_properties(physical_device);
+                    let features = this.get_physical_device_features(physical_device);
 
-                    PhysicalDevice::new(physical_device, mem_props, props, queue_families)
+                    PhysicalDevice::new(physical_device, features, mem_props, props, queue_families)
                 })
                 .filter(|physical_device| {
                     let major = vk::api_version_major(physical_device.props.api_version);
@@ -1,6 +1,8 @@
 mod accel_struct;
 mod buffer;
 mod cmd_buf;
+#[cfg(feature = "naga")]
+mod compiler;
 mod compute;
 mod descriptor_set;
 mod descriptor_set_layout;
@@ -11,9 +13,11 @@ mod instance;
 mod physical_device;
 mod ray_trace;
 mod render_pass;
+mod sampler;
 mod shader;
 mod surface;
 mod swapchain;
+mod worker;
 
 pub use {
     self::{
@@ -22,9 +26,14 @@ pub use {
             AccelerationStructureGeometryData, AccelerationStructureGeometryInfo,
             AccelerationStructureInfo, AccelerationStructureInfoBuilder, DeviceOrHostAddress,
         },
-        buffer::{Buffer, BufferInfo, BufferInfoBuilder, BufferSubresource},
+        buffer::{
+            Buffer, BufferInfo, BufferInfoBuilder, BufferSubresource, BufferView, BufferViewInfo,
+        },
         cmd_buf::CommandBuffer,
-        compute::{ComputePipeline, ComputePipelineInfo, ComputePipelineInfoBuilder},
+        compute::{
+            ComputePipeline, ComputePipelineInfo, ComputePipelineInfoBuilder,
+            ComputePipelineVariants,
+        },
         descriptor_set::{
             DescriptorPool, DescriptorPoolInfo, DescriptorPoolInfoBuilder, DescriptorPoolSize,
             DescriptorSet,
@@ -33,7 +42,8 @@ pub use {
         device::{Device, FeatureFlags},
         graphic::{
             BlendMode, DepthStencilMode, GraphicPipeline, GraphicPipelineInfo,
-            GraphicPipelineInfoBuilder, StencilMode, VertexInputState,
+            GraphicPipelineInfoBuilder, GraphicPipelineVariants, StencilMode, Vertex, VertexFormat,
+            VertexInputState,
         },
         image::{
             Image, ImageInfo, ImageInfoBuilder, ImageSubresource, ImageType, ImageView,
@@ -50,19 +60,28 @@ pub use {
             FramebufferKeyAttachment, RenderPass, RenderPassInfo, RenderPassInfoBuilder,
             SubpassDependency, SubpassDependencyBuilder, SubpassInfo,
         },
+        sampler::{Sampler, SamplerInfo, SamplerInfoBuilder},
         shader::{
-            DescriptorBinding, DescriptorBindingMap, DescriptorInfo, PipelineDescriptorInfo,
-            Shader, ShaderBuilder, SpecializationInfo,
+            BlockLayout, BlockMember, DescriptorAccess, DescriptorBinding, DescriptorBindingMap,
+            DescriptorBlockMap, DescriptorInfo, DescriptorNameMap, PipelineDescriptorInfo, Shader,
+            ShaderBuilder, SpecializationConstantMap, SpecializationInfo, SpecializationValue,
         },
         surface::Surface,
         swapchain::{
-            Swapchain, SwapchainError, SwapchainImage, SwapchainInfo, SwapchainInfoBuilder,
+            SurfaceFormatPreference, Swapchain, SwapchainError, SwapchainImage, SwapchainInfo,
+            SwapchainInfoBuilder,
         },
     },
+    archery,
     ash::{self, vk},
     vk_sync::{AccessType, ImageLayout},
 };
 
+#[cfg(feature = "naga")]
+pub use self::compiler::{
+    ShaderCompileError, ShaderCompileInfo, ShaderCompileInfoBuilder, ShaderDiagnostic,
+};
+
 use {
     archery::{SharedPointer, SharedPointerKind},
     derive_builder::Builder,
@@ -462,18 +481,20 @@ where
 
         debug!("selected: {:?}", physical_device);
 
-        let device = SharedPointer::new(Device::create(&instance, physical_device, cfg)?);
+        let device = SharedPointer::new(Device::create(&instance, physical_device, cfg.clone())?);
         let surface_formats = Device::surface_formats(&device, &surface)?;
 
         for fmt in &surface_formats {
             debug!("surface: {:#?} ({:#?})", fmt.format, fmt.color_space);
         }
 
-        // TODO: Explicitly fallback to BGRA_UNORM
-        let format = surface_formats
-            .into_iter()
-            .find(|format| Self::select_swapchain_format(*format))
-            .ok_or(DriverError::Unsupported)?;
+        let format = Self::select_swapchain_format(&cfg.surface_formats, &surface_formats)?;
+
+        info!(
+            "swapchain format: {:?} ({:?})",
+            format.format, format.color_space
+        );
+
         let swapchain = Swapchain::new(
             &device,
             surface,
@@ -481,7 +502,8 @@ where
                 desired_image_count: cfg.desired_swapchain_image_count,
                 format,
                 height,
-                sync_display: cfg.sync_display,
+                image_usage: cfg.swapchain_image_usage,
+                present_modes: cfg.present_modes,
                 width,
             },
         )?;
@@ -491,16 +513,37 @@ where
         Ok(Self { device, swapchain })
     }
 
-    fn select_swapchain_format(format: vk::SurfaceFormatKHR) -> bool {
-        // TODO: Properly handle the request for SRGB and swapchain image usage flags: The device may not support SRGB and only in that case do we fall back to UNORM
-        format.format == vk::Format::B8G8R8A8_UNORM
-            && format.color_space == vk::ColorSpaceKHR::SRGB_NONLINEAR
+    /// Returns the first supported format of the most preferred kind, or the first supported
+    /// format if none of the preferences are supported.
+    fn select_swapchain_format(
+        preferences: &[SurfaceFormatPreference],
+        surface_formats: &[vk::SurfaceFormatKHR],
+    ) -> Result<vk::SurfaceFormatKHR, DriverError> {
+        for preference in preferences {
+            if let Some(format) = surface_formats
+                .iter()
+                .copied()
+                .find(|format| preference.is_match(*format))
+            {
+                return Ok(format);
+            }
+        }
+
+        let format = surface_formats.first().copied().ok_or_else(|| {
+            warn!("no surface formats");
+
+            DriverError::Unsupported
+        })?;
+
+        warn!("no preferred surface format supported");
+
+        Ok(format)
     }
 }
 
 /// A list of required features. Features that are supported but not required will not be
 /// available.
-#[derive(Builder, Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Builder, Clone, Debug, Eq, Hash, PartialEq)]
 #[builder(pattern = "owned", derive(Debug))]
 pub struct DriverConfig {
     /// Enables Vulkan validation layers.
@@ -518,28 +561,62 @@ pub struct DriverConfig {
     #[builder(default = "3")]
     pub desired_swapchain_image_count: u32,
 
-    /// Determines if frames will be submitted to the display in a synchronous fashion or if they
-    /// should be displayed as fast as possible instead.
+    /// Enables task and mesh shader stages (`VK_EXT_mesh_shader`) for graphic pipelines.
+    #[builder(default)]
+    pub mesh_shading: bool,
+
+    /// Pipeline cache data, such as data previously returned by `Device::pipeline_cache_data`,
+    /// which seeds the pipeline cache of the device. Data created by a different physical device
+    /// or driver version is discarded.
+    #[builder(default, setter(strip_option))]
+    pub pipeline_cache_data: Option<Vec<u8>>,
+
+    /// Presentation modes, in order of preference; `FIFO_RELAXED` and then `FIFO` by default. If
+    /// none are supported `FIFO`, which is always supported, is used.
     ///
-    /// Turn on to eliminate visual tearing at the expense of latency.
-    #[builder(default = "true")]
-    pub sync_display: bool,
+    /// Use `FIFO` to eliminate visual tearing at the expense of latency, `MAILBOX` for low latency
+    /// without tearing, or `IMMEDIATE` to display frames as fast as possible.
+    #[builder(default = "vec![vk::PresentModeKHR::FIFO_RELAXED, vk::PresentModeKHR::FIFO]")]
+    pub present_modes: Vec<vk::PresentModeKHR>,
 
     #[builder(default = "true")]
     pub presentation: bool,
 
     #[builder(default)]
     pub ray_tracing: bool,
+
+    /// Kinds of surface format and color space, in order of preference, used to select the
+    /// swapchain format; `Unorm` and then `Srgb` by default. If none are supported the first
+    /// format supported by the surface is used.
+    ///
+    /// _NOTE:_ `Hdr10` and `ExtendedSrgbLinear` require an HDR display and
+    /// `VK_EXT_swapchain_colorspace`, which is enabled when available.
+    #[builder(default = "vec![SurfaceFormatPreference::Unorm, SurfaceFormatPreference::Srgb]")]
+    pub surface_formats: Vec<SurfaceFormatPreference>,
+
+    /// The usage of swapchain images; usage which is not supported by the surface or the selected
+    /// format (such as `STORAGE` with sRGB formats) is removed.
+    #[builder(default = "DriverConfig::DEFAULT_SWAPCHAIN_IMAGE_USAGE")]
+    pub swapchain_image_usage: vk::ImageUsageFlags,
 }
 
 impl DriverConfig {
+    const DEFAULT_SWAPCHAIN_IMAGE_USAGE: vk::ImageUsageFlags = vk::ImageUsageFlags::from_raw(
+        vk::ImageUsageFlags::COLOR_ATTACHMENT.as_raw()
+            | vk::ImageUsageFlags::SAMPLED.as_raw()
+            | vk::ImageUsageFlags::STORAGE.as_raw()
+            | vk::ImageUsageFlags::TRANSFER_DST.as_raw()
+            | vk::ImageUsageFlags::TRANSFER_SRC.as_raw(),
+    );
+
     #[allow(clippy::new_ret_no_self)]
     pub fn new() -> DriverConfigBuilder {
         Default::default()
     }
 
-    fn features(self) -> FeatureFlags {
+    fn features(&self) -> FeatureFlags {
         FeatureFlags {
+            mesh_shading: self.mesh_shading,
             presentation: true,
             ray_tracing: self.ray_tracing,
         }
@@ -547,7 +624,7 @@ impl DriverConfig {
 }
 
 // TODO: A more robust error type and some proper vk error mapping
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum DriverError {
     InvalidData,
     Unsupported,
@@ -562,6 +639,21 @@ impl Display for DriverError {
 
 impl Error for DriverError {}
 
+#[derive(Debug)]
+pub struct PhysicalDeviceMultiviewProperties {
+    pub max_multiview_instance_index: u32,
+    pub max_multiview_view_count: u32,
+}
+
+impl From<vk::PhysicalDeviceMultiviewProperties> for PhysicalDeviceMultiviewProperties {
+    fn from(props: vk::PhysicalDeviceMultiviewProperties) -> Self {
+        Self {
+            max_multiview_instance_index: props.max_multiview_instance_index,
+            max_multiview_view_count: props.max_multiview_view_count,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct PhysicalDeviceRayTracePipelineProperties {
     pub shader_group_handle_size: u32,
@@ -590,10 +682,3 @@ impl From<vk::PhysicalDeviceRayTracingPipelinePropertiesKHR>
         }
     }
 }
-
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-pub struct SamplerDesc {
-    pub address_modes: vk::SamplerAddressMode,
-    pub mipmap_mode: vk::SamplerMipmapMode,
-    pub texel_filter: vk::Filter,
-}
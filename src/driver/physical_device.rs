@@ -25,6 +25,7 @@ pub struct QueueFamilyProperties {
 
 #[derive(Clone)]
 pub struct PhysicalDevice {
+    pub features: vk::PhysicalDeviceFeatures,
     pub mem_props: vk::PhysicalDeviceMemoryProperties,
     physical_device: vk::PhysicalDevice,
     pub props: vk::PhysicalDeviceProperties,
@@ -34,11 +35,13 @@ pub struct PhysicalDevice {
 impl PhysicalDevice {
     pub fn new(
         physical_device: vk::PhysicalDevice,
+        features: vk::PhysicalDeviceFeatures,
         mem_props: vk::PhysicalDeviceMemoryProperties,
         props: vk::PhysicalDeviceProperties,
         queue_families: Vec<QueueFamily>,
     ) -> Self {
         Self {
+            features,
             mem_props,
             physical_device,
             props,
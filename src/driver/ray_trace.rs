@@ -1,10 +1,13 @@
 use {
-    super::{DescriptorBindingMap, Device, DriverError, PipelineDescriptorInfo, Shader},
+    super::{
+        BlockMember, DescriptorBindingMap, DescriptorBlockMap, DescriptorNameMap, Device,
+        DriverError, PipelineDescriptorInfo, Shader,
+    },
     archery::{SharedPointer, SharedPointerKind},
     ash::vk,
     derive_builder::Builder,
     log::warn,
-    std::{ffi::CString, ops::Deref, thread::panicking},
+    std::{ffi::CString, ops::Deref, slice::from_ref, thread::panicking},
 };
 
 #[derive(Debug)]
@@ -13,11 +16,15 @@ where
     P: SharedPointerKind,
 {
     pub descriptor_bindings: DescriptorBindingMap,
+    pub descriptor_blocks: DescriptorBlockMap,
     pub descriptor_info: PipelineDescriptorInfo<P>,
+    pub descriptor_names: DescriptorNameMap,
     device: SharedPointer<Device<P>, P>,
     pub info: RayTracePipelineInfo,
     pub layout: vk::PipelineLayout,
     pipeline: vk::Pipeline,
+    pub push_constant_block: Vec<BlockMember>,
+    pub push_constants: Option<vk::PushConstantRange>,
     shader_modules: Vec<vk::ShaderModule>,
 }
 
@@ -49,23 +56,57 @@ where
         let descriptor_bindings = Shader::merge_descriptor_bindings(
             shaders
                 .iter()
-                .map(|shader| shader.descriptor_bindings(device)),
+                .map(|shader| shader.descriptor_bindings(device))
+                .collect::<Result<Vec<_>, _>>()?,
         );
 
+        let descriptor_blocks = shaders
+            .iter()
+            .flat_map(|shader| shader.descriptor_blocks())
+            .collect();
         let descriptor_info = PipelineDescriptorInfo::create(device, &descriptor_bindings)?;
+        let descriptor_names =
+            Shader::merge_descriptor_names(shaders.iter().map(|shader| shader.descriptor_names()));
         let descriptor_set_layout_handles = descriptor_info
             .layouts
             .iter()
             .map(|(_, descriptor_set_layout)| **descriptor_set_layout)
             .collect::<Box<[_]>>();
 
+        // Stages which share the push constant block each report its members
+        let mut push_constant_block = shaders
+            .iter()
+            .flat_map(|shader| shader.push_constant_block())
+            .collect::<Vec<_>>();
+        push_constant_block.sort_by_key(|member| member.offset);
+        push_constant_block.dedup_by(|lhs, rhs| lhs.offset == rhs.offset && lhs.name == rhs.name);
+
+        // Ray tracing pipelines may have many shaders of the same stage, so a single range which
+        // covers every stage is used instead of one range per shader
+        let push_constants = shaders
+            .iter()
+            .filter_map(|shader| shader.push_constant_range())
+            .reduce(|lhs, rhs| {
+                let offset = lhs.offset.min(rhs.offset);
+                let end = (lhs.offset + lhs.size).max(rhs.offset + rhs.size);
+
+                vk::PushConstantRange {
+                    offset,
+                    size: end - offset,
+                    stage_flags: lhs.stage_flags | rhs.stage_flags,
+                }
+            });
+
         unsafe {
+            let mut layout_info =
+                vk::PipelineLayoutCreateInfo::builder().set_layouts(&descriptor_set_layout_handles);
+
+            if let Some(push_constants) = &push_constants {
+                layout_info = layout_info.push_constant_ranges(from_ref(push_constants));
+            }
+
             let layout = device
-                .create_pipeline_layout(
-                    &vk::PipelineLayoutCreateInfo::builder()
-                        .set_layouts(&descriptor_set_layout_handles),
-                    None,
-                )
+                .create_pipeline_layout(&layout_info, None)
                 .map_err(|err| {
                     warn!("{err}");
 
@@ -132,7 +173,7 @@ where
                 .unwrap()
                 .create_ray_tracing_pipelines(
                     vk::DeferredOperationKHR::null(),
-                    vk::PipelineCache::null(),
+                    device.pipeline_cache,
                     &[vk::RayTracingPipelineCreateInfoKHR::builder()
                         .stages(&shader_stages)
                         .groups(&shader_groups)
@@ -164,11 +205,15 @@ where
 
             Ok(Self {
                 descriptor_bindings,
+                descriptor_blocks,
                 descriptor_info,
+                descriptor_names,
                 device,
                 info,
                 layout,
                 pipeline,
+                push_constant_block,
+                push_constants,
                 shader_modules,
             })
         }
@@ -244,7 +289,7 @@ impl RayTracePipelineInfoBuilder {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct RayTraceShaderGroup {
     pub any_hit_shader: Option<u32>,
     pub closest_hit_shader: Option<u32>,
@@ -330,7 +375,7 @@ impl From<RayTraceShaderGroup> for vk::RayTracingShaderGroupCreateInfoKHR {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub enum RayTraceShaderGroupType {
     General,
     ProceduralHitGroup,
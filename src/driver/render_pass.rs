@@ -1,5 +1,10 @@
 use {
-    super::{DepthStencilMode, Device, DriverError, GraphicPipeline, SampleCount},
+    super::{
+        graphic::{MultisampleState, Stage},
+        worker::JobHandle,
+        DepthStencilMode, Device, DriverError, GraphicPipeline, GraphicPipelineInfo, SampleCount,
+        VertexInputState,
+    },
     archery::{SharedPointer, SharedPointerKind},
     ash::vk,
     derive_builder::Builder,
@@ -7,11 +12,132 @@ use {
     parking_lot::Mutex,
     std::{
         collections::{btree_map::Entry, BTreeMap},
+        mem::take,
         ops::Deref,
         thread::panicking,
     },
 };
 
+/// Creates a Vulkan render pass; used by `RenderPass` and when graphic pipelines are created ahead
+/// of time for a compatible render pass.
+pub(super) fn create_render_pass(
+    device: &ash::Device,
+    info: &RenderPassInfo,
+) -> Result<vk::RenderPass, DriverError> {
+    let attachments = info
+        .attachments
+        .iter()
+        .map(|attachment| attachment.into_vk())
+        .collect::<Box<[_]>>();
+    let dependencies = info
+        .dependencies
+        .iter()
+        .map(|dependency| dependency.into_vk())
+        .collect::<Box<[_]>>();
+
+    // This vec must stay alive until the create function completes; it holds the attachments that
+    // create info points to (ash builder lifetime)
+    let mut subpass_attachments_ref = vec![];
+
+    let mut subpasses = Vec::with_capacity(info.subpasses.len());
+    for (subpass, subpass_attachments) in info.subpasses.iter().map(move |subpass| {
+        let mut attachments = vec![];
+
+        let mut color_attachments = None;
+        if !subpass.color_attachments.is_empty() {
+            color_attachments =
+                Some(attachments.len()..attachments.len() + subpass.color_attachments.len());
+            attachments.extend(
+                subpass
+                    .color_attachments
+                    .iter()
+                    .copied()
+                    .map(|attachment| attachment.into_vk().build()),
+            );
+        }
+
+        let mut input_attachments = None;
+        if !subpass.input_attachments.is_empty() {
+            input_attachments =
+                Some(attachments.len()..attachments.len() + subpass.input_attachments.len());
+            attachments.extend(
+                subpass
+                    .input_attachments
+                    .iter()
+                    .copied()
+                    .map(|attachment| attachment.into_vk().build()),
+            );
+        }
+
+        let mut resolve_attachments = None;
+        if !subpass.resolve_attachments.is_empty() {
+            resolve_attachments =
+                Some(attachments.len()..attachments.len() + subpass.resolve_attachments.len());
+            attachments.extend(
+                subpass
+                    .resolve_attachments
+                    .iter()
+                    .copied()
+                    .map(|attachment| attachment.into_vk().build()),
+            );
+        }
+
+        let mut subpass_desc = vk::SubpassDescription2::builder()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .view_mask(subpass.view_mask);
+
+        if let Some(depth_stencil_attachment) = subpass.depth_stencil_attachment {
+            let idx = attachments.len();
+            attachments.push(depth_stencil_attachment.into_vk().build());
+            subpass_desc = subpass_desc.depth_stencil_attachment(&attachments[idx]);
+        }
+
+        let subpass = subpass_desc
+            .color_attachments(&attachments[color_attachments.unwrap_or_default()])
+            .input_attachments(&attachments[input_attachments.unwrap_or_default()])
+            .resolve_attachments(&attachments[resolve_attachments.unwrap_or_default()])
+            .preserve_attachments(&subpass.preserve_attachments)
+            .build();
+
+        (subpass, attachments)
+    }) {
+        subpasses.push(subpass);
+        subpass_attachments_ref.push(subpass_attachments);
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    let render_pass = unsafe {
+        device.create_render_pass2(
+            &vk::RenderPassCreateInfo2::builder()
+                .flags(vk::RenderPassCreateFlags::empty())
+                .attachments(&attachments)
+                .correlated_view_masks(&info.correlated_view_masks)
+                .dependencies(&dependencies)
+                .subpasses(&subpasses),
+            None,
+        )
+    };
+
+    // TODO: This needs some help, above, to get the correct types - also needs fixes in resolver!!!
+    #[cfg(target_os = "macos")]
+    let render_pass = unsafe {
+        device.create_render_pass(
+            &vk::RenderPassCreateInfo::builder()
+                .flags(vk::RenderPassCreateFlags::empty())
+                .attachments(&attachments)
+                .dependencies(&dependencies)
+                .subpasses(&subpasses),
+            None,
+        )
+    };
+
+    // Needs contributors (or hardware!) MoltenVK is about to goto 1.2 which makes this better
+    #[cfg(target_os = "macos")]
+    todo!("There is a description of this issue in the source code that caused this panic");
+
+    render_pass.map_err(|_| DriverError::InvalidData)
+}
+
 #[derive(Builder, Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[builder(build_fn(private, name = "fallible_build"), pattern = "owned")]
 pub struct AttachmentInfo {
@@ -98,6 +224,252 @@ pub struct FramebufferKeyAttachment {
     pub view_fmts: Vec<vk::Format>,
 }
 
+/// An owned copy of the state used to create a graphic pipeline, which may be sent to a worker
+/// thread.
+pub(super) struct GraphicPipelineDesc {
+    color_blend_attachment_states: Vec<vk::PipelineColorBlendAttachmentState>,
+    depth_stencil: Option<DepthStencilMode>,
+    extended_dynamic_state: bool,
+    info: GraphicPipelineInfo,
+    layout: vk::PipelineLayout,
+    multisample: MultisampleState,
+    render_pass: vk::RenderPass,
+    stage_flags: vk::ShaderStageFlags,
+    stages: Vec<Stage>,
+    subpass_idx: u32,
+    vertex_input: VertexInputState,
+}
+
+impl GraphicPipelineDesc {
+    pub(super) fn new<P>(
+        device: &Device<P>,
+        pipeline: &GraphicPipeline<P>,
+        render_pass: vk::RenderPass,
+        render_pass_info: &RenderPassInfo,
+        subpass_idx: u32,
+        depth_stencil: Option<DepthStencilMode>,
+    ) -> Self
+    where
+        P: SharedPointerKind,
+    {
+        let color_blend_attachment_states = render_pass_info.subpasses[subpass_idx as usize]
+            .color_attachments
+            .iter()
+            .map(|attachment| {
+                pipeline
+                    .info
+                    .attachment_blends
+                    .get(&attachment.attachment)
+                    .unwrap_or(&pipeline.info.blend)
+                    .into_vk()
+            })
+            .collect();
+
+        Self {
+            color_blend_attachment_states,
+            depth_stencil,
+            extended_dynamic_state: device.extended_dynamic_state_ext.is_some(),
+            info: pipeline.info.clone(),
+            layout: pipeline.state.layout,
+            multisample: pipeline.state.multisample.clone(),
+            render_pass,
+            stage_flags: pipeline.stages(),
+            stages: pipeline.state.stages.clone(),
+            subpass_idx,
+            vertex_input: pipeline.state.vertex_input.clone(),
+        }
+    }
+
+    /// Creates the pipeline; this may be called from any thread.
+    pub(super) fn create(
+        &self,
+        device: &ash::Device,
+        pipeline_cache: vk::PipelineCache,
+    ) -> Result<vk::Pipeline, DriverError> {
+        use std::slice::from_ref;
+
+        let color_blend_state = vk::PipelineColorBlendStateCreateInfo::builder()
+            .attachments(&self.color_blend_attachment_states)
+            .blend_constants(
+                self.info
+                    .blend_constants
+                    .map(|blend_constants| blend_constants.map(|constant| *constant))
+                    .unwrap_or_default(),
+            )
+            .logic_op(self.info.logic_op.unwrap_or(vk::LogicOp::COPY))
+            .logic_op_enable(self.info.logic_op.is_some());
+        let mut dynamic_states = vec![vk::DynamicState::SCISSOR, vk::DynamicState::VIEWPORT];
+
+        // The resolver sets these states to match the static pipeline state, and then `Draw` may
+        // override them
+        if self.info.dynamic_state {
+            dynamic_states.extend([
+                vk::DynamicState::DEPTH_BIAS,
+                vk::DynamicState::DEPTH_BOUNDS,
+                vk::DynamicState::STENCIL_COMPARE_MASK,
+                vk::DynamicState::STENCIL_REFERENCE,
+                vk::DynamicState::STENCIL_WRITE_MASK,
+            ]);
+
+            if self.extended_dynamic_state {
+                dynamic_states.extend([
+                    vk::DynamicState::CULL_MODE_EXT,
+                    vk::DynamicState::DEPTH_COMPARE_OP_EXT,
+                    vk::DynamicState::DEPTH_TEST_ENABLE_EXT,
+                    vk::DynamicState::DEPTH_WRITE_ENABLE_EXT,
+                    vk::DynamicState::FRONT_FACE_EXT,
+                ]);
+            }
+        }
+
+        if self.info.blend_constants.is_none() {
+            dynamic_states.push(vk::DynamicState::BLEND_CONSTANTS);
+        }
+
+        if self.info.line_width.is_none() {
+            dynamic_states.push(vk::DynamicState::LINE_WIDTH);
+        }
+
+        let dynamic_state =
+            vk::PipelineDynamicStateCreateInfo::builder().dynamic_states(&dynamic_states);
+        let multisample_state = vk::PipelineMultisampleStateCreateInfo::builder()
+            .alpha_to_coverage_enable(self.multisample.alpha_to_coverage_enable)
+            .alpha_to_one_enable(self.multisample.alpha_to_one_enable)
+            .flags(self.multisample.flags)
+            .min_sample_shading(self.multisample.min_sample_shading)
+            .rasterization_samples(self.multisample.rasterization_samples.into_vk())
+            .sample_shading_enable(self.multisample.sample_shading_enable)
+            .sample_mask(&self.multisample.sample_mask);
+        let mut specializations = Vec::with_capacity(self.stages.len());
+        let stages = self
+            .stages
+            .iter()
+            .map(|stage| {
+                let mut info = vk::PipelineShaderStageCreateInfo::builder()
+                    .module(stage.module)
+                    .name(&stage.name)
+                    .stage(stage.flags);
+
+                if let Some(specialization_info) = &stage.specialization_info {
+                    specializations.push(
+                        vk::SpecializationInfo::builder()
+                            .map_entries(&specialization_info.map_entries)
+                            .data(&specialization_info.data)
+                            .build(),
+                    );
+
+                    info = info.specialization_info(specializations.last().unwrap());
+                }
+
+                info.build()
+            })
+            .collect::<Box<[_]>>();
+        let vertex_input_state = vk::PipelineVertexInputStateCreateInfo::builder()
+            .vertex_attribute_descriptions(&self.vertex_input.vertex_attribute_descriptions)
+            .vertex_binding_descriptions(&self.vertex_input.vertex_binding_descriptions);
+        let viewport_state = vk::PipelineViewportStateCreateInfo::builder()
+            .viewport_count(1)
+            .scissor_count(1);
+        let has_tesselation_stage = self
+            .stage_flags
+            .contains(vk::ShaderStageFlags::TESSELLATION_CONTROL);
+        let input_assembly_state = vk::PipelineInputAssemblyStateCreateInfo {
+            primitive_restart_enable: self.info.primitive_restart as _,
+            topology: if has_tesselation_stage {
+                vk::PrimitiveTopology::PATCH_LIST
+            } else {
+                self.info.topology
+            },
+            ..Default::default()
+        };
+        let mut tessellation_domain_origin_state =
+            vk::PipelineTessellationDomainOriginStateCreateInfo::builder()
+                .domain_origin(self.info.tessellation_domain_origin);
+        let tessellation_state = vk::PipelineTessellationStateCreateInfo::builder()
+            .patch_control_points(self.info.patch_control_points)
+            .push_next(&mut tessellation_domain_origin_state);
+        let depth_stencil = self
+            .depth_stencil
+            .map(|depth_stencil| depth_stencil.into_vk())
+            .unwrap_or_default();
+        let rasterization_state = vk::PipelineRasterizationStateCreateInfo {
+            depth_bias_enable: self.info.dynamic_state as _, // Zero unless set using `Draw`
+            front_face: self.info.front_face,
+            line_width: self
+                .info
+                .line_width
+                .map(|line_width| *line_width)
+                .unwrap_or(1.0),
+            polygon_mode: self.info.polygon_mode,
+            cull_mode: self.info.cull_mode,
+            ..Default::default()
+        };
+        let mut graphic_pipeline_info = vk::GraphicsPipelineCreateInfo::builder()
+            .color_blend_state(&color_blend_state)
+            .depth_stencil_state(&depth_stencil)
+            .dynamic_state(&dynamic_state)
+            .layout(self.layout)
+            .multisample_state(&multisample_state)
+            .rasterization_state(&rasterization_state)
+            .render_pass(self.render_pass)
+            .stages(&stages)
+            .subpass(self.subpass_idx)
+            .viewport_state(&viewport_state);
+
+        if has_tesselation_stage {
+            graphic_pipeline_info = graphic_pipeline_info.tessellation_state(&tessellation_state);
+        }
+
+        // Mesh pipelines do not use vertex input or input assembly state
+        if !self.stage_flags.contains(vk::ShaderStageFlags::MESH_EXT) {
+            graphic_pipeline_info = graphic_pipeline_info
+                .input_assembly_state(&input_assembly_state)
+                .vertex_input_state(&vertex_input_state);
+        }
+
+        unsafe {
+            device.create_graphics_pipelines(pipeline_cache, from_ref(&graphic_pipeline_info), None)
+        }
+        .map_err(|(_, err)| {
+            warn!(
+                "create_graphics_pipelines: {err}\n{:#?}",
+                graphic_pipeline_info.build()
+            );
+
+            DriverError::Unsupported
+        })
+        .map(|pipelines| pipelines[0])
+    }
+}
+
+#[derive(Debug)]
+enum GraphicPipelineEntry<P>
+where
+    P: SharedPointerKind,
+{
+    /// Creation failed; the error is kept so that it is reported once instead of retrying every
+    /// time the pipeline is used.
+    Failed(DriverError),
+    Pending(
+        JobHandle<Result<vk::Pipeline, DriverError>>,
+        SharedPointer<GraphicPipeline<P>, P>,
+    ),
+    Ready(vk::Pipeline),
+}
+
+impl<P> GraphicPipelineEntry<P>
+where
+    P: SharedPointerKind,
+{
+    fn wait(self) -> Result<vk::Pipeline, DriverError> {
+        match self {
+            Self::Failed(err) => Err(err),
+            Self::Pending(job, _) => job.wait().unwrap_or(Err(DriverError::Unsupported)),
+            Self::Ready(pipeline) => Ok(pipeline),
+        }
+    }
+}
+
 #[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
 struct GraphicPipelineKey {
     depth_stencil: Option<DepthStencilMode>,
@@ -109,6 +481,13 @@ struct GraphicPipelineKey {
 #[builder(pattern = "owned", derive(Debug))]
 pub struct RenderPassInfo {
     pub attachments: Vec<AttachmentInfo>,
+
+    /// Sets of views which may be rendered concurrently when multiview is used.
+    ///
+    /// _NOTE:_ This is an optimization hint only; it does not change the results of rendering.
+    #[builder(default)]
+    pub correlated_view_masks: Vec<u32>,
+
     pub subpasses: Vec<SubpassInfo>,
     pub dependencies: Vec<SubpassDependency>,
 }
@@ -127,7 +506,7 @@ where
 {
     device: SharedPointer<Device<P>, P>,
     framebuffer_cache: Mutex<BTreeMap<FramebufferKey, vk::Framebuffer>>,
-    graphic_pipeline_cache: Mutex<BTreeMap<GraphicPipelineKey, vk::Pipeline>>,
+    graphic_pipeline_cache: Mutex<BTreeMap<GraphicPipelineKey, GraphicPipelineEntry<P>>>,
     pub info: RenderPassInfo,
     render_pass: vk::RenderPass,
 }
@@ -146,116 +525,7 @@ where
         trace!("create");
 
         let device = SharedPointer::clone(device);
-        let attachments = info
-            .attachments
-            .iter()
-            .map(|attachment| attachment.into_vk())
-            .collect::<Box<[_]>>();
-        let dependencies = info
-            .dependencies
-            .iter()
-            .map(|dependency| dependency.into_vk())
-            .collect::<Box<[_]>>();
-
-        // This vec must stay alive until the create function completes; it holds the attachments that
-        // create info points to (ash builder lifetime)
-        let mut subpass_attachments_ref = vec![];
-
-        let mut subpasses = Vec::with_capacity(info.subpasses.len());
-        for (subpass, subpass_attachments) in info.subpasses.iter().map(move |subpass| {
-            let mut attachments = vec![];
-
-            let mut color_attachments = None;
-            if !subpass.color_attachments.is_empty() {
-                color_attachments =
-                    Some(attachments.len()..attachments.len() + subpass.color_attachments.len());
-                attachments.extend(
-                    subpass
-                        .color_attachments
-                        .iter()
-                        .copied()
-                        .map(|attachment| attachment.into_vk().build()),
-                );
-            }
-
-            let mut input_attachments = None;
-            if !subpass.input_attachments.is_empty() {
-                input_attachments =
-                    Some(attachments.len()..attachments.len() + subpass.input_attachments.len());
-                attachments.extend(
-                    subpass
-                        .input_attachments
-                        .iter()
-                        .copied()
-                        .map(|attachment| attachment.into_vk().build()),
-                );
-            }
-
-            let mut resolve_attachments = None;
-            if !subpass.resolve_attachments.is_empty() {
-                resolve_attachments =
-                    Some(attachments.len()..attachments.len() + subpass.resolve_attachments.len());
-                attachments.extend(
-                    subpass
-                        .resolve_attachments
-                        .iter()
-                        .copied()
-                        .map(|attachment| attachment.into_vk().build()),
-                );
-            }
-
-            let mut subpass_desc = vk::SubpassDescription2::builder()
-                .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS);
-
-            if let Some(depth_stencil_attachment) = subpass.depth_stencil_attachment {
-                let idx = attachments.len();
-                attachments.push(depth_stencil_attachment.into_vk().build());
-                subpass_desc = subpass_desc.depth_stencil_attachment(&attachments[idx]);
-            }
-
-            let subpass = subpass_desc
-                .color_attachments(&attachments[color_attachments.unwrap_or_default()])
-                .input_attachments(&attachments[input_attachments.unwrap_or_default()])
-                .resolve_attachments(&attachments[resolve_attachments.unwrap_or_default()])
-                .preserve_attachments(&subpass.preserve_attachments)
-                .build();
-
-            (subpass, attachments)
-        }) {
-            subpasses.push(subpass);
-            subpass_attachments_ref.push(subpass_attachments);
-        }
-
-        #[cfg(not(target_os = "macos"))]
-        let render_pass = unsafe {
-            device.create_render_pass2(
-                &vk::RenderPassCreateInfo2::builder()
-                    .flags(vk::RenderPassCreateFlags::empty())
-                    .attachments(&attachments)
-                    .dependencies(&dependencies)
-                    .subpasses(&subpasses),
-                None,
-            )
-        };
-
-        // TODO: This needs some help, above, to get the correct types - also needs fixes in resolver!!!
-        #[cfg(target_os = "macos")]
-        let render_pass = unsafe {
-            device.create_render_pass(
-                &vk::RenderPassCreateInfo::builder()
-                    .flags(vk::RenderPassCreateFlags::empty())
-                    .attachments(&attachments)
-                    .dependencies(&dependencies)
-                    .subpasses(&subpasses),
-                None,
-            )
-        };
-
-        // Needs contributors (or hardware!) MoltenVK is about to goto 1.2 which makes this better
-        #[cfg(target_os = "macos")]
-        todo!("There is a description of this issue in the source code that caused this panic");
-
-        let render_pass = render_pass.map_err(|_| DriverError::InvalidData)?;
+        let render_pass = create_render_pass(&device, &info)?;
 
         Ok(Self {
             info,
@@ -321,128 +591,108 @@ where
         Ok(framebuffer)
     }
 
+    /// Returns the Vulkan pipeline used to draw the given graphic pipeline in a subpass of this
+    /// render pass, creating it if required.
+    ///
+    /// If the pipeline is being created on a worker thread (see
+    /// [`RenderPass::try_graphic_pipeline_ref`]) this function waits for it.
     pub fn graphic_pipeline_ref(
         &self,
         pipeline: &SharedPointer<GraphicPipeline<P>, P>,
         depth_stencil: Option<DepthStencilMode>,
         subpass_idx: u32,
     ) -> Result<vk::Pipeline, DriverError> {
-        use std::slice::from_ref;
+        self.graphic_pipeline(pipeline, depth_stencil, subpass_idx, true)
+            .map(|pipeline| pipeline.unwrap())
+    }
 
-        let mut cache = self.graphic_pipeline_cache.lock();
-        let entry = cache.entry(GraphicPipelineKey {
+    fn graphic_pipeline(
+        &self,
+        pipeline: &SharedPointer<GraphicPipeline<P>, P>,
+        depth_stencil: Option<DepthStencilMode>,
+        subpass_idx: u32,
+        wait: bool,
+    ) -> Result<Option<vk::Pipeline>, DriverError> {
+        let key = GraphicPipelineKey {
             depth_stencil,
             layout: pipeline.layout,
             subpass_idx,
-        });
-        if let Entry::Occupied(entry) = entry {
-            return Ok(*entry.get());
-        }
-
-        let entry = match entry {
-            Entry::Vacant(entry) => entry,
-            _ => unreachable!(),
         };
+        let mut cache = self.graphic_pipeline_cache.lock();
+        let is_pending = match cache.get(&key) {
+            Some(GraphicPipelineEntry::Failed(err)) => return Err(*err),
+            Some(GraphicPipelineEntry::Pending(job, _)) => {
+                if !wait && !job.is_finished() {
+                    return Ok(None);
+                }
 
-        let color_blend_attachment_states = self
-            .info
-            .attachments
-            .iter()
-            .map(|_| pipeline.info.blend.into_vk())
-            .collect::<Box<[_]>>();
-        let color_blend_state = vk::PipelineColorBlendStateCreateInfo::builder()
-            .attachments(&color_blend_attachment_states);
-        let dynamic_states = [vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
-        let dynamic_state =
-            vk::PipelineDynamicStateCreateInfo::builder().dynamic_states(&dynamic_states);
-        let multisample_state = vk::PipelineMultisampleStateCreateInfo::builder()
-            .alpha_to_coverage_enable(pipeline.state.multisample.alpha_to_coverage_enable)
-            .alpha_to_one_enable(pipeline.state.multisample.alpha_to_one_enable)
-            .flags(pipeline.state.multisample.flags)
-            .min_sample_shading(pipeline.state.multisample.min_sample_shading)
-            .rasterization_samples(pipeline.state.multisample.rasterization_samples.into_vk())
-            .sample_shading_enable(pipeline.state.multisample.sample_shading_enable)
-            .sample_mask(&pipeline.state.multisample.sample_mask);
-        let mut specializations = Vec::with_capacity(pipeline.state.stages.len());
-        let stages = pipeline
-            .state
-            .stages
-            .iter()
-            .map(|stage| {
-                let mut info = vk::PipelineShaderStageCreateInfo::builder()
-                    .module(stage.module)
-                    .name(&stage.name)
-                    .stage(stage.flags);
+                true
+            }
+            Some(GraphicPipelineEntry::Ready(pipeline)) => return Ok(Some(*pipeline)),
+            None => false,
+        };
 
-                if let Some(specialization_info) = &stage.specialization_info {
-                    specializations.push(
-                        vk::SpecializationInfo::builder()
-                            .map_entries(&specialization_info.map_entries)
-                            .data(&specialization_info.data)
-                            .build(),
-                    );
+        if is_pending {
+            return match cache.remove(&key).unwrap().wait() {
+                Ok(pipeline) => {
+                    cache.insert(key, GraphicPipelineEntry::Ready(pipeline));
 
-                    info = info.specialization_info(specializations.last().unwrap());
+                    Ok(Some(pipeline))
                 }
+                Err(err) => {
+                    warn!("unable to create graphic pipeline: {err}");
 
-                info.build()
-            })
-            .collect::<Box<[_]>>();
-        let vertex_input_state = vk::PipelineVertexInputStateCreateInfo::builder()
-            .vertex_attribute_descriptions(
-                &pipeline.state.vertex_input.vertex_attribute_descriptions,
-            )
-            .vertex_binding_descriptions(&pipeline.state.vertex_input.vertex_binding_descriptions);
-        let viewport_state = vk::PipelineViewportStateCreateInfo::builder()
-            .viewport_count(1)
-            .scissor_count(1);
-        let input_assembly_state = vk::PipelineInputAssemblyStateCreateInfo {
-            topology: vk::PrimitiveTopology::TRIANGLE_LIST,
-            ..Default::default()
-        };
-        let depth_stencil = depth_stencil
-            .map(|depth_stencil| depth_stencil.into_vk())
-            .unwrap_or_default();
-        let rasterization_state = vk::PipelineRasterizationStateCreateInfo {
-            front_face: pipeline.info.front_face,
-            line_width: 1.0,
-            polygon_mode: pipeline.info.polygon_mode,
-            cull_mode: pipeline.info.cull_mode,
-            ..Default::default()
-        };
-        let graphic_pipeline_info = vk::GraphicsPipelineCreateInfo::builder()
-            .color_blend_state(&color_blend_state)
-            .depth_stencil_state(&depth_stencil)
-            .dynamic_state(&dynamic_state)
-            .input_assembly_state(&input_assembly_state)
-            .layout(pipeline.state.layout)
-            .multisample_state(&multisample_state)
-            .rasterization_state(&rasterization_state)
-            .render_pass(self.render_pass)
-            .stages(&stages)
-            .subpass(subpass_idx)
-            .vertex_input_state(&vertex_input_state)
-            .viewport_state(&viewport_state);
+                    cache.insert(key, GraphicPipelineEntry::Failed(err));
 
-        let pipeline = unsafe {
-            self.device.create_graphics_pipelines(
-                vk::PipelineCache::null(),
-                from_ref(&graphic_pipeline_info),
-                None,
-            )
+                    Err(err)
+                }
+            };
         }
-        .map_err(|(_, err)| {
-            warn!(
-                "create_graphics_pipelines: {err}\n{:#?}",
-                graphic_pipeline_info.build()
-            );
 
-            DriverError::Unsupported
-        })?[0];
+        let desc = GraphicPipelineDesc::new(
+            &self.device,
+            pipeline,
+            self.render_pass,
+            &self.info,
+            subpass_idx,
+            depth_stencil,
+        );
+
+        if wait {
+            let pipeline = desc.create(&self.device, self.device.pipeline_cache)?;
+            cache.insert(key, GraphicPipelineEntry::Ready(pipeline));
+
+            Ok(Some(pipeline))
+        } else {
+            let device = (**self.device).clone();
+            let pipeline_cache = self.device.pipeline_cache;
+            let job = self
+                .device
+                .workers
+                .submit(move || desc.create(&device, pipeline_cache));
+
+            // The graphic pipeline owns the shader modules and layout used by the worker thread
+            cache.insert(
+                key,
+                GraphicPipelineEntry::Pending(job, SharedPointer::clone(pipeline)),
+            );
 
-        entry.insert(pipeline);
+            Ok(None)
+        }
+    }
 
-        Ok(pipeline)
+    /// Returns the Vulkan pipeline used to draw the given graphic pipeline in a subpass of this
+    /// render pass, or `None` if it is not ready yet.
+    ///
+    /// Pipelines which are not ready are created on a worker thread, and this function does not
+    /// block.
+    pub fn try_graphic_pipeline_ref(
+        &self,
+        pipeline: &SharedPointer<GraphicPipeline<P>, P>,
+        depth_stencil: Option<DepthStencilMode>,
+        subpass_idx: u32,
+    ) -> Result<Option<vk::Pipeline>, DriverError> {
+        self.graphic_pipeline(pipeline, depth_stencil, subpass_idx, false)
     }
 }
 
@@ -471,8 +721,10 @@ where
                 self.device.destroy_framebuffer(framebuffer, None);
             }
 
-            for pipeline in self.graphic_pipeline_cache.lock().values().copied() {
-                self.device.destroy_pipeline(pipeline, None);
+            for entry in take(self.graphic_pipeline_cache.get_mut()).into_values() {
+                if let Ok(pipeline) = entry.wait() {
+                    self.device.destroy_pipeline(pipeline, None);
+                }
             }
 
             self.device.destroy_render_pass(self.render_pass, None);
@@ -525,6 +777,12 @@ pub struct SubpassInfo {
     pub input_attachments: Vec<AttachmentRef>,
     pub preserve_attachments: Vec<u32>,
     pub resolve_attachments: Vec<AttachmentRef>,
+
+    /// Bitmask of the views (attachment array layers) rendered by this subpass, or zero to
+    /// disable multiview.
+    ///
+    /// _NOTE:_ All subpasses of a render pass must either use multiview or not.
+    pub view_mask: u32,
 }
 
 impl SubpassInfo {
@@ -535,9 +793,11 @@ impl SubpassInfo {
             input_attachments: Vec::with_capacity(capacity),
             preserve_attachments: Vec::with_capacity(capacity),
             resolve_attachments: Vec::with_capacity(capacity),
+            view_mask: 0,
         }
     }
 
+    /// Returns `true` if this subpass writes to more than one color or depth/stencil attachment.
     pub fn has_multiple_attachments(&self) -> bool {
         let count = self.depth_stencil_attachment.is_some() as usize + self.color_attachments.len();
 
@@ -0,0 +1,237 @@
+use {
+    super::{Device, DriverError},
+    archery::{SharedPointer, SharedPointerKind},
+    ash::vk,
+    derive_builder::Builder,
+    log::{trace, warn},
+    ordered_float::OrderedFloat,
+    std::{
+        fmt::{Debug, Formatter},
+        ops::Deref,
+        thread::panicking,
+    },
+};
+
+/// Smart pointer handle to a [sampler] object.
+///
+/// Samplers may be bound to a render graph as nodes and then used to fill `sampler` descriptors
+/// (separate samplers) of a pipeline.
+///
+/// [sampler]: https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/VkSampler.html
+pub struct Sampler<P>
+where
+    P: SharedPointerKind,
+{
+    device: SharedPointer<Device<P>, P>,
+    pub info: SamplerInfo,
+    sampler: vk::Sampler,
+}
+
+impl<P> Sampler<P>
+where
+    P: SharedPointerKind,
+{
+    pub fn create(
+        device: &SharedPointer<Device<P>, P>,
+        info: impl Into<SamplerInfo>,
+    ) -> Result<Self, DriverError> {
+        let info = info.into();
+
+        trace!("create: {:?}", info);
+
+        let device = SharedPointer::clone(device);
+        let sampler = unsafe { Self::create_vk(&device, info) }?;
+
+        Ok(Self {
+            device,
+            info,
+            sampler,
+        })
+    }
+
+    /// Creates a raw sampler; the caller is responsible for destroying it.
+    pub(super) unsafe fn create_vk(
+        device: &Device<P>,
+        mut info: SamplerInfo,
+    ) -> Result<vk::Sampler, DriverError> {
+        // Anisotropy is clamped to whatever the device supports
+        let max_anisotropy = device.physical_device.props.limits.max_sampler_anisotropy;
+        if *info.max_anisotropy > max_anisotropy {
+            info.max_anisotropy = OrderedFloat(max_anisotropy);
+        }
+
+        device.create_sampler(&info.into_vk(), None).map_err(|err| {
+            warn!("{err}");
+
+            DriverError::Unsupported
+        })
+    }
+}
+
+impl<P> Debug for Sampler<P>
+where
+    P: SharedPointerKind,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.sampler)
+    }
+}
+
+impl<P> Deref for Sampler<P>
+where
+    P: SharedPointerKind,
+{
+    type Target = vk::Sampler;
+
+    fn deref(&self) -> &Self::Target {
+        &self.sampler
+    }
+}
+
+impl<P> Drop for Sampler<P>
+where
+    P: SharedPointerKind,
+{
+    fn drop(&mut self) {
+        if panicking() {
+            return;
+        }
+
+        unsafe {
+            self.device.destroy_sampler(self.sampler, None);
+        }
+    }
+}
+
+/// Information used to create a [`Sampler`] instance or an immutable sampler of a pipeline.
+///
+/// The default value is a trilinear, repeating sampler using 16x anisotropic filtering.
+#[derive(Builder, Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[builder(
+    build_fn(private, name = "fallible_build"),
+    derive(Debug),
+    pattern = "owned"
+)]
+pub struct SamplerInfo {
+    /// Addressing mode for `U` coordinates outside of the `[0..1]` range.
+    #[builder(default = "vk::SamplerAddressMode::REPEAT")]
+    pub address_mode_u: vk::SamplerAddressMode,
+
+    /// Addressing mode for `V` coordinates outside of the `[0..1]` range.
+    #[builder(default = "vk::SamplerAddressMode::REPEAT")]
+    pub address_mode_v: vk::SamplerAddressMode,
+
+    /// Addressing mode for `W` coordinates outside of the `[0..1]` range.
+    #[builder(default = "vk::SamplerAddressMode::REPEAT")]
+    pub address_mode_w: vk::SamplerAddressMode,
+
+    /// Enables anisotropic filtering.
+    #[builder(default = "true")]
+    pub anisotropy_enable: bool,
+
+    /// Predefined border color used when an address mode is `CLAMP_TO_BORDER`.
+    #[builder(default = "vk::BorderColor::FLOAT_TRANSPARENT_BLACK")]
+    pub border_color: vk::BorderColor,
+
+    /// Enables comparison against a reference value during lookups (depth samplers).
+    #[builder(default)]
+    pub compare_enable: bool,
+
+    /// Comparison operator used when `compare_enable` is set.
+    #[builder(default = "vk::CompareOp::NEVER")]
+    pub compare_op: vk::CompareOp,
+
+    /// Magnification filter.
+    #[builder(default = "vk::Filter::LINEAR")]
+    pub mag_filter: vk::Filter,
+
+    /// Anisotropy value clamp used when `anisotropy_enable` is set.
+    ///
+    /// _NOTE:_ This value is clamped to the limit of the device.
+    #[builder(default = "OrderedFloat(16.0)", setter(into))]
+    pub max_anisotropy: OrderedFloat<f32>,
+
+    /// Maximum level-of-detail clamp; use `vk::LOD_CLAMP_NONE` to use all mip levels.
+    #[builder(default = "OrderedFloat(vk::LOD_CLAMP_NONE)", setter(into))]
+    pub max_lod: OrderedFloat<f32>,
+
+    /// Minification filter.
+    #[builder(default = "vk::Filter::LINEAR")]
+    pub min_filter: vk::Filter,
+
+    /// Minimum level-of-detail clamp.
+    #[builder(default, setter(into))]
+    pub min_lod: OrderedFloat<f32>,
+
+    /// Bias added to mipmap level-of-detail calculations.
+    #[builder(default, setter(into))]
+    pub mip_lod_bias: OrderedFloat<f32>,
+
+    /// Mipmap filter.
+    #[builder(default = "vk::SamplerMipmapMode::LINEAR")]
+    pub mipmap_mode: vk::SamplerMipmapMode,
+
+    /// Enables texel coordinates in the `[0..width)` range instead of normalized coordinates.
+    #[builder(default)]
+    pub unnormalized_coordinates: bool,
+}
+
+impl SamplerInfo {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> SamplerInfoBuilder {
+        SamplerInfoBuilder::default()
+    }
+
+    pub(super) fn into_vk(self) -> vk::SamplerCreateInfo {
+        vk::SamplerCreateInfo {
+            mag_filter: self.mag_filter,
+            min_filter: self.min_filter,
+            mipmap_mode: self.mipmap_mode,
+            address_mode_u: self.address_mode_u,
+            address_mode_v: self.address_mode_v,
+            address_mode_w: self.address_mode_w,
+            mip_lod_bias: *self.mip_lod_bias,
+            anisotropy_enable: self.anisotropy_enable as _,
+            max_anisotropy: *self.max_anisotropy,
+            compare_enable: self.compare_enable as _,
+            compare_op: self.compare_op,
+            min_lod: *self.min_lod,
+            max_lod: *self.max_lod,
+            border_color: self.border_color,
+            unnormalized_coordinates: self.unnormalized_coordinates as _,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for SamplerInfo {
+    fn default() -> Self {
+        Self::new().build()
+    }
+}
+
+// HACK: https://github.com/colin-kiegel/rust-derive-builder/issues/56
+impl SamplerInfoBuilder {
+    /// Sets the addressing mode of all three (`U`, `V`, and `W`) coordinates.
+    pub fn address_mode(self, address_mode: vk::SamplerAddressMode) -> Self {
+        self.address_mode_u(address_mode)
+            .address_mode_v(address_mode)
+            .address_mode_w(address_mode)
+    }
+
+    pub fn build(self) -> SamplerInfo {
+        self.fallible_build()
+            .expect("All required fields set at initialization")
+    }
+
+    /// Sets both the magnification and minification filters.
+    pub fn texel_filter(self, texel_filter: vk::Filter) -> Self {
+        self.mag_filter(texel_filter).min_filter(texel_filter)
+    }
+}
+
+impl From<SamplerInfoBuilder> for SamplerInfo {
+    fn from(info: SamplerInfoBuilder) -> Self {
+        info.build()
+    }
+}
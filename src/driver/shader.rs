@@ -1,69 +1,265 @@
 use {
-    super::{DescriptorSetLayout, Device, DriverError, SamplerDesc, VertexInputState},
+    super::{DescriptorSetLayout, Device, DriverError, SamplerInfo, VertexInputState},
     archery::{SharedPointer, SharedPointerKind},
     ash::vk,
+    bytemuck::Pod,
     derive_builder::Builder,
-    log::{debug, error, info, trace},
+    log::{debug, error, info, trace, warn},
     spirq::{
-        ty::{ScalarType, Type},
+        ty::{ScalarType, StructType, Type},
         DescriptorType, EntryPoint, ReflectConfig, Variable,
     },
     std::{
         collections::{btree_map::BTreeMap, HashMap},
         fmt::{Debug, Formatter},
+        hash::{Hash, Hasher},
         iter::repeat,
+        mem::size_of,
     },
+    vk_sync::AccessType,
 };
 
 pub type DescriptorBindingMap = BTreeMap<DescriptorBinding, (DescriptorInfo, vk::ShaderStageFlags)>;
+pub type DescriptorBlockMap = BTreeMap<DescriptorBinding, Vec<BlockMember>>;
+pub type DescriptorNameMap = BTreeMap<String, DescriptorBinding>;
+pub type SpecializationConstantMap = BTreeMap<String, (u32, Type)>;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum VertexNumericType {
+    Float,
+    Signed,
+    Unsigned,
+}
+
+/// Returns the shader-visible numeric type and size in bytes of common vertex attribute formats.
+///
+/// Normalized and scaled formats are seen as floating point values by shaders.
+fn vertex_format_layout(fmt: vk::Format) -> Option<(VertexNumericType, u32)> {
+    use {vk::Format as F, VertexNumericType::*};
+
+    Some(match fmt {
+        F::R8_UNORM | F::R8_SNORM | F::R8_USCALED | F::R8_SSCALED => (Float, 1),
+        F::R8_UINT => (Unsigned, 1),
+        F::R8_SINT => (Signed, 1),
+        F::R8G8_UNORM | F::R8G8_SNORM | F::R8G8_USCALED | F::R8G8_SSCALED => (Float, 2),
+        F::R8G8_UINT => (Unsigned, 2),
+        F::R8G8_SINT => (Signed, 2),
+        F::R8G8B8_UNORM | F::R8G8B8_SNORM | F::R8G8B8_USCALED | F::R8G8B8_SSCALED => (Float, 3),
+        F::R8G8B8_UINT => (Unsigned, 3),
+        F::R8G8B8_SINT => (Signed, 3),
+        F::R8G8B8A8_UNORM
+        | F::R8G8B8A8_SNORM
+        | F::R8G8B8A8_USCALED
+        | F::R8G8B8A8_SSCALED
+        | F::B8G8R8A8_UNORM
+        | F::A8B8G8R8_UNORM_PACK32
+        | F::A8B8G8R8_SNORM_PACK32
+        | F::A2R10G10B10_UNORM_PACK32
+        | F::A2B10G10R10_UNORM_PACK32
+        | F::A2B10G10R10_SNORM_PACK32
+        | F::B10G11R11_UFLOAT_PACK32 => (Float, 4),
+        F::R8G8B8A8_UINT | F::A8B8G8R8_UINT_PACK32 | F::A2B10G10R10_UINT_PACK32 => (Unsigned, 4),
+        F::R8G8B8A8_SINT | F::A8B8G8R8_SINT_PACK32 | F::A2B10G10R10_SINT_PACK32 => (Signed, 4),
+        F::R16_UNORM | F::R16_SNORM | F::R16_USCALED | F::R16_SSCALED | F::R16_SFLOAT => (Float, 2),
+        F::R16_UINT => (Unsigned, 2),
+        F::R16_SINT => (Signed, 2),
+        F::R16G16_UNORM
+        | F::R16G16_SNORM
+        | F::R16G16_USCALED
+        | F::R16G16_SSCALED
+        | F::R16G16_SFLOAT => (Float, 4),
+        F::R16G16_UINT => (Unsigned, 4),
+        F::R16G16_SINT => (Signed, 4),
+        F::R16G16B16_UNORM
+        | F::R16G16B16_SNORM
+        | F::R16G16B16_USCALED
+        | F::R16G16B16_SSCALED
+        | F::R16G16B16_SFLOAT => (Float, 6),
+        F::R16G16B16_UINT => (Unsigned, 6),
+        F::R16G16B16_SINT => (Signed, 6),
+        F::R16G16B16A16_UNORM
+        | F::R16G16B16A16_SNORM
+        | F::R16G16B16A16_USCALED
+        | F::R16G16B16A16_SSCALED
+        | F::R16G16B16A16_SFLOAT => (Float, 8),
+        F::R16G16B16A16_UINT => (Unsigned, 8),
+        F::R16G16B16A16_SINT => (Signed, 8),
+        F::R32_SFLOAT => (Float, 4),
+        F::R32_UINT => (Unsigned, 4),
+        F::R32_SINT => (Signed, 4),
+        F::R32G32_SFLOAT => (Float, 8),
+        F::R32G32_UINT => (Unsigned, 8),
+        F::R32G32_SINT => (Signed, 8),
+        F::R32G32B32_SFLOAT => (Float, 12),
+        F::R32G32B32_UINT => (Unsigned, 12),
+        F::R32G32B32_SINT => (Signed, 12),
+        F::R32G32B32A32_SFLOAT => (Float, 16),
+        F::R32G32B32A32_UINT => (Unsigned, 16),
+        F::R32G32B32A32_SINT => (Signed, 16),
+        F::R64_SFLOAT => (Float, 8),
+        F::R64G64_SFLOAT => (Float, 16),
+        F::R64G64B64_SFLOAT => (Float, 24),
+        F::R64G64B64A64_SFLOAT => (Float, 32),
+        _ => return None,
+    })
+}
 
-fn guess_immutable_sampler(
-    device: &Device<impl SharedPointerKind>,
-    binding_name: &str,
-) -> vk::Sampler {
+/// Returns the size in bytes of one texel of common buffer and vertex formats.
+pub(super) fn format_texel_size(fmt: vk::Format) -> Option<u32> {
+    vertex_format_layout(fmt).map(|(_, size)| size)
+}
+
+/// Parses the sampler naming convention used by image bindings without an explicit sampler:
+/// `_sampler_xyz` where `x` is the texel filter (`n`earest or `l`inear), `y` is the mipmap mode
+/// (`n`earest or `l`inear), and `z` is the address mode (`b`order, `e`dge, `m`irrored, or
+/// `r`epeat).
+fn guess_immutable_sampler(binding_name: &str) -> SamplerInfo {
     const INVALID_ERR: &str = "Invalid sampler specification";
 
-    let (texel_filter, mipmap_mode, address_modes) = if binding_name.contains("_sampler_") {
+    let mut info = SamplerInfo::new();
+
+    if binding_name.contains("_sampler_") {
         let spec = &binding_name[binding_name.len() - 3..];
-        let texel_filter = match &spec[0..1] {
-            "n" => vk::Filter::NEAREST,
-            "l" => vk::Filter::LINEAR,
-            _ => panic!("{INVALID_ERR}: {}", &spec[0..1]),
-        };
 
-        let mipmap_mode = match &spec[1..2] {
-            "n" => vk::SamplerMipmapMode::NEAREST,
-            "l" => vk::SamplerMipmapMode::LINEAR,
-            _ => panic!("{INVALID_ERR}: {}", &spec[1..2]),
-        };
+        match &spec[0..1] {
+            "n" => {
+                info = info
+                    .texel_filter(vk::Filter::NEAREST)
+                    .anisotropy_enable(false)
+            }
+            "l" => info = info.texel_filter(vk::Filter::LINEAR),
+            _ => warn!("{INVALID_ERR}: {binding_name}"),
+        }
 
-        let address_modes = match &spec[2..3] {
-            "b" => vk::SamplerAddressMode::CLAMP_TO_BORDER,
-            "e" => vk::SamplerAddressMode::CLAMP_TO_EDGE,
-            "m" => vk::SamplerAddressMode::MIRRORED_REPEAT,
-            "r" => vk::SamplerAddressMode::REPEAT,
-            _ => panic!("{INVALID_ERR}: {}", &spec[2..3]),
-        };
+        match &spec[1..2] {
+            "n" => info = info.mipmap_mode(vk::SamplerMipmapMode::NEAREST),
+            "l" => info = info.mipmap_mode(vk::SamplerMipmapMode::LINEAR),
+            _ => warn!("{INVALID_ERR}: {binding_name}"),
+        }
 
-        (texel_filter, mipmap_mode, address_modes)
+        match &spec[2..3] {
+            "b" => info = info.address_mode(vk::SamplerAddressMode::CLAMP_TO_BORDER),
+            "e" => info = info.address_mode(vk::SamplerAddressMode::CLAMP_TO_EDGE),
+            "m" => info = info.address_mode(vk::SamplerAddressMode::MIRRORED_REPEAT),
+            "r" => info = info.address_mode(vk::SamplerAddressMode::REPEAT),
+            _ => warn!("{INVALID_ERR}: {binding_name}"),
+        }
     } else {
         debug!("image binding {binding_name} using default sampler");
+    }
 
-        (
-            vk::Filter::LINEAR,
-            vk::SamplerMipmapMode::LINEAR,
-            vk::SamplerAddressMode::REPEAT,
-        )
-    };
+    info.build()
+}
+
+fn spec_constants(entry_point: &EntryPoint) -> SpecializationConstantMap {
+    entry_point
+        .vars
+        .iter()
+        .filter_map(|var| match var {
+            Variable::SpecConstant {
+                name: Some(name),
+                spec_id,
+                ty,
+            } => Some((name.clone(), (*spec_id, ty.clone()))),
+            _ => None,
+        })
+        .collect()
+}
+
+/// A member of a push constant, uniform, or storage block, as reflected from shader code.
+///
+/// Members of nested structures are flattened and named using `.`, such as `light.color`.
+#[derive(Clone, Debug)]
+pub struct BlockMember {
+    /// The member name, or an empty string if the shader code does not name it.
+    pub name: String,
+
+    /// The offset, in bytes, from the start of the block.
+    pub offset: u32,
+
+    /// The size in bytes; zero for runtime-sized arrays.
+    pub size: u32,
 
-    Device::immutable_sampler(
-        device,
-        SamplerDesc {
-            texel_filter,
-            mipmap_mode,
-            address_modes,
-        },
-    )
+    /// The reflected type, including array and matrix strides.
+    pub ty: Type,
+}
+
+impl BlockMember {
+    fn flatten(prefix: &str, base_offset: usize, ty: &StructType, res: &mut Vec<Self>) {
+        for member in &ty.members {
+            let name = member.name.as_deref().unwrap_or_default();
+            let name = if prefix.is_empty() {
+                name.to_owned()
+            } else {
+                format!("{prefix}.{name}")
+            };
+            let offset = base_offset + member.offset;
+
+            if let Type::Struct(ty) = &member.ty {
+                Self::flatten(&name, offset, ty, res);
+            } else {
+                res.push(Self {
+                    name,
+                    offset: offset as _,
+                    size: member.ty.nbyte().unwrap_or_default() as _,
+                    ty: member.ty.clone(),
+                });
+            }
+        }
+    }
+}
+
+/// A `#[repr(C)]` type which may be checked against a reflected push constant, uniform, or storage
+/// block.
+///
+/// Usually implemented using `#[derive(BlockLayout)]` from the `screen-13-derive` crate.
+pub trait BlockLayout: Pod {
+    /// Returns the name and offset, in bytes, of each field of this type.
+    fn block_fields() -> Vec<(&'static str, u32)>;
+
+    /// Checks that the size and field offsets of this type match the given block members.
+    ///
+    /// Fields match the member with the same name, or the first member of a nested structure with
+    /// the same name.
+    fn validate_block(members: &[BlockMember]) -> Result<(), DriverError> {
+        let block_end = members
+            .iter()
+            .map(|member| member.offset + member.size)
+            .max()
+            .unwrap_or_default();
+        let size = size_of::<Self>() as u32;
+
+        // Rust types may include explicit padding up to the std140 base alignment of the block
+        if size < block_end || size > (block_end + 15) & !15 {
+            warn!("block size is {block_end} bytes, type size is {size} bytes");
+
+            return Err(DriverError::InvalidData);
+        }
+
+        for (field, offset) in Self::block_fields() {
+            let nested_prefix = format!("{field}.");
+            let member = members
+                .iter()
+                .filter(|member| member.name == field || member.name.starts_with(&nested_prefix))
+                .min_by_key(|member| member.offset)
+                .ok_or_else(|| {
+                    warn!("field `{field}` not found in block");
+
+                    DriverError::InvalidData
+                })?;
+
+            if member.offset != offset {
+                warn!(
+                    "field `{field}` is at offset {offset}, block member `{}` is at offset {}",
+                    member.name, member.offset
+                );
+
+                return Err(DriverError::InvalidData);
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// Tuple of descriptor set index and binding index.
@@ -73,6 +269,45 @@ fn guess_immutable_sampler(
 #[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
 pub struct DescriptorBinding(pub u32, pub u32);
 
+/// Access qualifiers (`readonly` or `writeonly`) of a storage descriptor, as reflected from shader
+/// code.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum DescriptorAccess {
+    ReadOnly,
+    ReadWrite,
+    WriteOnly,
+}
+
+impl DescriptorAccess {
+    /// Returns `true` if the descriptor is read by shader code.
+    pub fn is_read(self) -> bool {
+        matches!(self, Self::ReadOnly | Self::ReadWrite)
+    }
+
+    /// Returns `true` if the descriptor is written by shader code.
+    pub fn is_write(self) -> bool {
+        matches!(self, Self::ReadWrite | Self::WriteOnly)
+    }
+
+    fn merge(self, other: Self) -> Self {
+        if self == other {
+            self
+        } else {
+            Self::ReadWrite
+        }
+    }
+}
+
+impl From<spirq::AccessType> for DescriptorAccess {
+    fn from(access_ty: spirq::AccessType) -> Self {
+        match access_ty {
+            spirq::AccessType::ReadOnly => Self::ReadOnly,
+            spirq::AccessType::ReadWrite => Self::ReadWrite,
+            spirq::AccessType::WriteOnly => Self::WriteOnly,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum DescriptorInfo {
     AccelerationStructure(u32),
@@ -80,10 +315,10 @@ pub enum DescriptorInfo {
     InputAttachment(u32, u32), //count, input index,
     SampledImage(u32),
     Sampler(u32),
-    StorageBuffer(u32),
+    StorageBuffer(u32, DescriptorAccess),
     StorageBufferDynamic(u32),
-    StorageImage(u32),
-    StorageTexelBuffer(u32),
+    StorageImage(u32, DescriptorAccess),
+    StorageTexelBuffer(u32, DescriptorAccess),
     UniformBuffer(u32),
     UniformBufferDynamic(u32),
     UniformTexelBuffer(u32),
@@ -97,16 +332,112 @@ impl DescriptorInfo {
             Self::InputAttachment(binding_count, _) => binding_count,
             Self::SampledImage(binding_count) => binding_count,
             Self::Sampler(binding_count) => binding_count,
-            Self::StorageBuffer(binding_count) => binding_count,
+            Self::StorageBuffer(binding_count, _) => binding_count,
             Self::StorageBufferDynamic(binding_count) => binding_count,
-            Self::StorageImage(binding_count) => binding_count,
-            Self::StorageTexelBuffer(binding_count) => binding_count,
+            Self::StorageImage(binding_count, _) => binding_count,
+            Self::StorageTexelBuffer(binding_count, _) => binding_count,
             Self::UniformBuffer(binding_count) => binding_count,
             Self::UniformBufferDynamic(binding_count) => binding_count,
             Self::UniformTexelBuffer(binding_count) => binding_count,
         }
     }
 
+    /// Returns the access type of this descriptor when used by the given shader stages, using the
+    /// reflected access qualifiers of storage descriptors.
+    ///
+    /// Returns `None` for input attachments, which may be color or depth/stencil images.
+    pub fn access_type(self, stages: vk::ShaderStageFlags) -> Option<AccessType> {
+        use AccessType::*;
+
+        const RAY_TRACE_STAGES: vk::ShaderStageFlags = vk::ShaderStageFlags::from_raw(
+            vk::ShaderStageFlags::RAYGEN_KHR.as_raw()
+                | vk::ShaderStageFlags::ANY_HIT_KHR.as_raw()
+                | vk::ShaderStageFlags::CLOSEST_HIT_KHR.as_raw()
+                | vk::ShaderStageFlags::MISS_KHR.as_raw()
+                | vk::ShaderStageFlags::INTERSECTION_KHR.as_raw()
+                | vk::ShaderStageFlags::CALLABLE_KHR.as_raw(),
+        );
+
+        let access = match self {
+            Self::AccelerationStructure(_) => {
+                return Some(if RAY_TRACE_STAGES.contains(stages) {
+                    RayTracingShaderReadAccelerationStructure
+                } else {
+                    AnyShaderReadOther
+                });
+            }
+            Self::InputAttachment(..) => return None,
+            Self::StorageBuffer(_, access)
+            | Self::StorageImage(_, access)
+            | Self::StorageTexelBuffer(_, access) => access,
+            Self::StorageBufferDynamic(_) => DescriptorAccess::ReadWrite,
+            _ => DescriptorAccess::ReadOnly,
+        };
+
+        Some(if access.is_write() {
+            match stages {
+                vk::ShaderStageFlags::COMPUTE => ComputeShaderWrite,
+                vk::ShaderStageFlags::FRAGMENT => FragmentShaderWrite,
+                vk::ShaderStageFlags::GEOMETRY => GeometryShaderWrite,
+                vk::ShaderStageFlags::TESSELLATION_CONTROL => TessellationControlShaderWrite,
+                vk::ShaderStageFlags::TESSELLATION_EVALUATION => TessellationEvaluationShaderWrite,
+                vk::ShaderStageFlags::VERTEX => VertexShaderWrite,
+                _ => AnyShaderWrite,
+            }
+        } else if matches!(self, Self::UniformBuffer(_) | Self::UniformBufferDynamic(_)) {
+            match stages {
+                vk::ShaderStageFlags::COMPUTE => ComputeShaderReadUniformBuffer,
+                vk::ShaderStageFlags::FRAGMENT => FragmentShaderReadUniformBuffer,
+                vk::ShaderStageFlags::GEOMETRY => GeometryShaderReadUniformBuffer,
+                vk::ShaderStageFlags::TESSELLATION_CONTROL => {
+                    TessellationControlShaderReadUniformBuffer
+                }
+                vk::ShaderStageFlags::TESSELLATION_EVALUATION => {
+                    TessellationEvaluationShaderReadUniformBuffer
+                }
+                vk::ShaderStageFlags::VERTEX => VertexShaderReadUniformBuffer,
+                _ => AnyShaderReadUniformBuffer,
+            }
+        } else if matches!(
+            self,
+            Self::StorageBuffer(..) | Self::StorageImage(..) | Self::StorageTexelBuffer(..)
+        ) {
+            match stages {
+                vk::ShaderStageFlags::COMPUTE => ComputeShaderReadOther,
+                vk::ShaderStageFlags::FRAGMENT => FragmentShaderReadOther,
+                vk::ShaderStageFlags::GEOMETRY => GeometryShaderReadOther,
+                vk::ShaderStageFlags::TESSELLATION_CONTROL => TessellationControlShaderReadOther,
+                vk::ShaderStageFlags::TESSELLATION_EVALUATION => {
+                    TessellationEvaluationShaderReadOther
+                }
+                vk::ShaderStageFlags::VERTEX => VertexShaderReadOther,
+                _ if RAY_TRACE_STAGES.contains(stages) => RayTracingShaderReadOther,
+                _ => AnyShaderReadOther,
+            }
+        } else {
+            match stages {
+                vk::ShaderStageFlags::COMPUTE => ComputeShaderReadSampledImageOrUniformTexelBuffer,
+                vk::ShaderStageFlags::FRAGMENT => {
+                    FragmentShaderReadSampledImageOrUniformTexelBuffer
+                }
+                vk::ShaderStageFlags::GEOMETRY => {
+                    GeometryShaderReadSampledImageOrUniformTexelBuffer
+                }
+                vk::ShaderStageFlags::TESSELLATION_CONTROL => {
+                    TessellationControlShaderReadSampledImageOrUniformTexelBuffer
+                }
+                vk::ShaderStageFlags::TESSELLATION_EVALUATION => {
+                    TessellationEvaluationShaderReadSampledImageOrUniformTexelBuffer
+                }
+                vk::ShaderStageFlags::VERTEX => VertexShaderReadSampledImageOrUniformTexelBuffer,
+                _ if RAY_TRACE_STAGES.contains(stages) => {
+                    RayTracingShaderReadSampledImageOrUniformTexelBuffer
+                }
+                _ => AnyShaderReadSampledImageOrUniformTexelBuffer,
+            }
+        })
+    }
+
     pub fn sampler(self) -> Option<vk::Sampler> {
         match self {
             Self::CombinedImageSampler(_, sampler) => Some(sampler),
@@ -123,10 +454,10 @@ impl From<DescriptorInfo> for vk::DescriptorType {
             DescriptorInfo::InputAttachment(..) => Self::INPUT_ATTACHMENT,
             DescriptorInfo::SampledImage(_) => Self::SAMPLED_IMAGE,
             DescriptorInfo::Sampler(_) => Self::SAMPLER,
-            DescriptorInfo::StorageBuffer(_) => Self::STORAGE_BUFFER,
+            DescriptorInfo::StorageBuffer(..) => Self::STORAGE_BUFFER,
             DescriptorInfo::StorageBufferDynamic(_) => Self::STORAGE_BUFFER_DYNAMIC,
-            DescriptorInfo::StorageImage(_) => Self::STORAGE_IMAGE,
-            DescriptorInfo::StorageTexelBuffer(_) => Self::STORAGE_TEXEL_BUFFER,
+            DescriptorInfo::StorageImage(..) => Self::STORAGE_IMAGE,
+            DescriptorInfo::StorageTexelBuffer(..) => Self::STORAGE_TEXEL_BUFFER,
             DescriptorInfo::UniformBuffer(_) => Self::UNIFORM_BUFFER,
             DescriptorInfo::UniformBufferDynamic(_) => Self::UNIFORM_BUFFER_DYNAMIC,
             DescriptorInfo::UniformTexelBuffer(_) => Self::UNIFORM_TEXEL_BUFFER,
@@ -236,6 +567,22 @@ pub struct Shader {
     pub spirv: Vec<u8>,
     pub stage: vk::ShaderStageFlags,
 
+    /// Explicit samplers used by the combined image sampler bindings of this shader.
+    ///
+    /// Bindings not found here use a sampler guessed from the binding name; see
+    /// [`ShaderBuilder::image_sampler`].
+    #[builder(default, setter(custom))]
+    pub image_samplers: BTreeMap<DescriptorBinding, SamplerInfo>,
+
+    /// Explicit vertex input layout of this vertex shader, used instead of the layout reflected
+    /// from the shader code.
+    ///
+    /// Allows multiple bindings, per-instance input rates, custom strides and offsets, and packed
+    /// formats such as `R8G8B8A8_UNORM`. The layout is checked against the reflected shader inputs
+    /// during pipeline creation.
+    #[builder(default, setter(strip_option))]
+    pub vertex_layout: Option<VertexInputState>,
+
     entry_point: EntryPoint,
 }
 
@@ -296,6 +643,13 @@ impl Shader {
         Self::new(vk::ShaderStageFlags::INTERSECTION_KHR, spirv)
     }
 
+    /// Creates a new mesh shader.
+    ///
+    /// _NOTE:_ May panic if the shader code is invalid.
+    pub fn new_mesh(spirv: impl ShaderCode) -> ShaderBuilder {
+        Self::new(vk::ShaderStageFlags::MESH_EXT, spirv)
+    }
+
     /// Creates a new ray trace shader.
     ///
     /// _NOTE:_ May panic if the shader code is invalid.
@@ -310,6 +664,13 @@ impl Shader {
         Self::new(vk::ShaderStageFlags::RAYGEN_KHR, spirv)
     }
 
+    /// Creates a new task shader.
+    ///
+    /// _NOTE:_ May panic if the shader code is invalid.
+    pub fn new_task(spirv: impl ShaderCode) -> ShaderBuilder {
+        Self::new(vk::ShaderStageFlags::TASK_EXT, spirv)
+    }
+
     /// Creates a new tesselation control shader.
     ///
     /// _NOTE:_ May panic if the shader code is invalid.
@@ -356,7 +717,7 @@ impl Shader {
     pub fn descriptor_bindings(
         &self,
         device: &Device<impl SharedPointerKind>,
-    ) -> DescriptorBindingMap {
+    ) -> Result<DescriptorBindingMap, DriverError> {
         let mut res = DescriptorBindingMap::default();
 
         for (name, binding, desc_ty, binding_count) in
@@ -384,23 +745,33 @@ impl Shader {
                 DescriptorType::AccelStruct() => {
                     DescriptorInfo::AccelerationStructure(binding_count)
                 }
-                DescriptorType::CombinedImageSampler() => DescriptorInfo::CombinedImageSampler(
-                    binding_count,
-                    guess_immutable_sampler(device, name.as_deref().expect("invalid binding name")),
-                ),
+                DescriptorType::CombinedImageSampler() => {
+                    let sampler_info = self
+                        .image_samplers
+                        .get(&DescriptorBinding(binding.set(), binding.bind()))
+                        .copied()
+                        .unwrap_or_else(|| {
+                            guess_immutable_sampler(name.as_deref().unwrap_or_default())
+                        });
+
+                    DescriptorInfo::CombinedImageSampler(
+                        binding_count,
+                        Device::immutable_sampler(device, sampler_info)?,
+                    )
+                }
                 DescriptorType::InputAttachment(attachment) => {
                     DescriptorInfo::InputAttachment(binding_count, *attachment)
                 }
                 DescriptorType::SampledImage() => DescriptorInfo::SampledImage(binding_count),
                 DescriptorType::Sampler() => DescriptorInfo::Sampler(binding_count),
-                DescriptorType::StorageBuffer(_access_ty) => {
-                    DescriptorInfo::StorageBuffer(binding_count)
+                DescriptorType::StorageBuffer(access_ty) => {
+                    DescriptorInfo::StorageBuffer(binding_count, (*access_ty).into())
                 }
-                DescriptorType::StorageImage(_access_ty) => {
-                    DescriptorInfo::StorageImage(binding_count)
+                DescriptorType::StorageImage(access_ty) => {
+                    DescriptorInfo::StorageImage(binding_count, (*access_ty).into())
                 }
-                DescriptorType::StorageTexelBuffer(_access_ty) => {
-                    DescriptorInfo::StorageTexelBuffer(binding_count)
+                DescriptorType::StorageTexelBuffer(access_ty) => {
+                    DescriptorInfo::StorageTexelBuffer(binding_count, (*access_ty).into())
                 }
                 DescriptorType::UniformBuffer() => DescriptorInfo::UniformBuffer(binding_count),
                 DescriptorType::UniformTexelBuffer() => {
@@ -413,9 +784,49 @@ impl Shader {
             );
         }
 
+        Ok(res)
+    }
+
+    /// Returns the reflected members of each uniform and storage buffer block of this shader.
+    pub fn descriptor_blocks(&self) -> DescriptorBlockMap {
+        let mut res = DescriptorBlockMap::default();
+
+        for (binding, ty) in self.entry_point.vars.iter().filter_map(|var| match var {
+            Variable::Descriptor {
+                desc_bind,
+                desc_ty: DescriptorType::StorageBuffer(_) | DescriptorType::UniformBuffer(),
+                ty: Type::Struct(ty),
+                ..
+            } => Some((desc_bind, ty)),
+            _ => None,
+        }) {
+            let mut members = vec![];
+            BlockMember::flatten("", 0, ty, &mut members);
+            res.insert(DescriptorBinding(binding.set(), binding.bind()), members);
+        }
+
         res
     }
 
+    /// Returns the set and binding index of each named descriptor variable of this shader.
+    pub fn descriptor_names(&self) -> DescriptorNameMap {
+        self.entry_point
+            .vars
+            .iter()
+            .filter_map(|var| match var {
+                Variable::Descriptor {
+                    name: Some(name),
+                    desc_bind,
+                    ..
+                } => Some((
+                    name.clone(),
+                    DescriptorBinding(desc_bind.set(), desc_bind.bind()),
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn merge_descriptor_bindings(
         descriptor_bindings: impl IntoIterator<Item = DescriptorBindingMap>,
     ) -> DescriptorBindingMap {
@@ -469,9 +880,10 @@ impl Shader {
                         panic!("{INVALID_ERR}");
                     }
                 }
-                DescriptorInfo::StorageBuffer(lhs) => {
-                    if let DescriptorInfo::StorageBuffer(rhs) = rhs {
+                DescriptorInfo::StorageBuffer(lhs, lhs_access) => {
+                    if let DescriptorInfo::StorageBuffer(rhs, rhs_access) = rhs {
                         *lhs = rhs.max(*lhs);
+                        *lhs_access = lhs_access.merge(rhs_access);
                     } else {
                         panic!("{INVALID_ERR}");
                     }
@@ -483,16 +895,18 @@ impl Shader {
                         panic!("{INVALID_ERR}");
                     }
                 }
-                DescriptorInfo::StorageImage(lhs) => {
-                    if let DescriptorInfo::StorageImage(rhs) = rhs {
+                DescriptorInfo::StorageImage(lhs, lhs_access) => {
+                    if let DescriptorInfo::StorageImage(rhs, rhs_access) = rhs {
                         *lhs = rhs.max(*lhs);
+                        *lhs_access = lhs_access.merge(rhs_access);
                     } else {
                         panic!("{INVALID_ERR}");
                     }
                 }
-                DescriptorInfo::StorageTexelBuffer(lhs) => {
-                    if let DescriptorInfo::StorageTexelBuffer(rhs) = rhs {
+                DescriptorInfo::StorageTexelBuffer(lhs, lhs_access) => {
+                    if let DescriptorInfo::StorageTexelBuffer(rhs, rhs_access) = rhs {
                         *lhs = rhs.max(*lhs);
+                        *lhs_access = lhs_access.merge(rhs_access);
                     } else {
                         panic!("{INVALID_ERR}");
                     }
@@ -541,6 +955,56 @@ impl Shader {
         res
     }
 
+    /// Merges the descriptor names of each shader of a pipeline.
+    ///
+    /// Names which refer to different descriptors in different shaders are ambiguous and are not
+    /// included in the result, so that they cannot be used to bind the wrong descriptor.
+    pub fn merge_descriptor_names(
+        descriptor_names: impl IntoIterator<Item = DescriptorNameMap>,
+    ) -> DescriptorNameMap {
+        let mut res = DescriptorNameMap::new();
+        let mut ambiguous_names = vec![];
+
+        for (name, descriptor_binding) in descriptor_names.into_iter().flatten() {
+            match res.get(&name) {
+                Some(existing_binding) if *existing_binding != descriptor_binding => {
+                    warn!(
+                        "ambiguous descriptor name `{name}`: {existing_binding:?} and \
+                        {descriptor_binding:?}"
+                    );
+
+                    ambiguous_names.push(name);
+                }
+                _ => {
+                    res.insert(name, descriptor_binding);
+                }
+            }
+        }
+
+        for name in &ambiguous_names {
+            res.remove(name);
+        }
+
+        res
+    }
+
+    /// Returns the reflected members of the push constant block of this shader.
+    pub fn push_constant_block(&self) -> Vec<BlockMember> {
+        let mut res = vec![];
+
+        for var in &self.entry_point.vars {
+            if let Variable::PushConstant {
+                ty: Type::Struct(ty),
+                ..
+            } = var
+            {
+                BlockMember::flatten("", 0, ty, &mut res);
+            }
+        }
+
+        res
+    }
+
     pub fn push_constant_range(&self) -> Option<vk::PushConstantRange> {
         self.entry_point
             .vars
@@ -600,34 +1064,210 @@ impl Shader {
         Ok(entry_point)
     }
 
-    pub fn vertex_input(&self) -> VertexInputState {
-        fn scalar_format(ty: &ScalarType, byte_len: u32) -> vk::Format {
-            match ty {
-                ScalarType::Float(_) => match byte_len {
-                    4 => vk::Format::R32_SFLOAT,
-                    8 => vk::Format::R32G32_SFLOAT,
-                    12 => vk::Format::R32G32B32_SFLOAT,
-                    16 => vk::Format::R32G32B32A32_SFLOAT,
-                    _ => unimplemented!("byte_len {byte_len}"),
-                },
-                ScalarType::Signed(_) => match byte_len {
-                    4 => vk::Format::R32_SINT,
-                    8 => vk::Format::R32G32_SINT,
-                    12 => vk::Format::R32G32B32_SINT,
-                    16 => vk::Format::R32G32B32A32_SINT,
-                    _ => unimplemented!("byte_len {byte_len}"),
-                },
-                ScalarType::Unsigned(_) => match byte_len {
-                    4 => vk::Format::R32_UINT,
-                    8 => vk::Format::R32G32_UINT,
-                    12 => vk::Format::R32G32B32_UINT,
-                    16 => vk::Format::R32G32B32A32_UINT,
-                    _ => unimplemented!("byte_len {byte_len}"),
-                },
-                _ => unimplemented!("{:?}", ty),
+    /// Returns the constant ID and reflected type of each named specialization constant of this
+    /// shader.
+    pub fn specialization_constants(&self) -> SpecializationConstantMap {
+        spec_constants(&self.entry_point)
+    }
+
+    /// Returns a copy of this shader using the given specialization info.
+    pub(super) fn specialized(
+        &self,
+        specialization_info: SpecializationInfo,
+    ) -> Result<Self, DriverError> {
+        let entry_point =
+            Self::reflect_entry_point(&self.entry_name, &self.spirv, Some(&specialization_info))?;
+
+        Ok(Self {
+            entry_point,
+            specialization_info: Some(specialization_info),
+            ..self.clone()
+        })
+    }
+
+    /// Checks that the explicit vertex layout of this shader, if any, provides every input reflected
+    /// from the shader code using a compatible format.
+    pub fn validate_vertex_layout(&self) -> Result<(), DriverError> {
+        let vertex_layout = match &self.vertex_layout {
+            Some(vertex_layout) => vertex_layout,
+            None => return Ok(()),
+        };
+
+        for attribute in &vertex_layout.vertex_attribute_descriptions {
+            if vertex_layout
+                .vertex_attribute_descriptions
+                .iter()
+                .filter(|other| other.location == attribute.location)
+                .count()
+                > 1
+            {
+                warn!("duplicate vertex attribute location {}", attribute.location);
+
+                return Err(DriverError::InvalidData);
+            }
+
+            let binding = vertex_layout
+                .vertex_binding_descriptions
+                .iter()
+                .find(|binding| binding.binding == attribute.binding)
+                .ok_or_else(|| {
+                    warn!(
+                        "vertex attribute location {} uses undescribed binding {}",
+                        attribute.location, attribute.binding
+                    );
+
+                    DriverError::InvalidData
+                })?;
+
+            if let Some((_, byte_len)) = vertex_format_layout(attribute.format) {
+                if binding.stride != 0 && attribute.offset + byte_len > binding.stride {
+                    warn!(
+                        "vertex attribute location {} ({:?} at offset {}) exceeds stride {} of \
+                        binding {}",
+                        attribute.location,
+                        attribute.format,
+                        attribute.offset,
+                        binding.stride,
+                        binding.binding
+                    );
+
+                    return Err(DriverError::InvalidData);
+                }
+            }
+        }
+
+        for (name, location, ty) in self.entry_point.vars.iter().filter_map(|var| match var {
+            Variable::Input { name, location, ty } => Some((name, location, ty)),
+            _ => None,
+        }) {
+            let (location, _) = location.into_inner();
+            let scalar_ty = match ty {
+                Type::Scalar(ty) => ty,
+                Type::Vector(ty) => &ty.scalar_ty,
+                // Matrix inputs span multiple locations; these are not checked
+                _ => continue,
+            };
+            let attribute = vertex_layout
+                .vertex_attribute_descriptions
+                .iter()
+                .find(|attribute| attribute.location == location)
+                .ok_or_else(|| {
+                    warn!("vertex input location {location} ({name:?}) not found in vertex layout");
+
+                    DriverError::InvalidData
+                })?;
+
+            if let Some((numeric_ty, _)) = vertex_format_layout(attribute.format) {
+                let is_compatible = matches!(
+                    (scalar_ty, numeric_ty),
+                    (ScalarType::Float(_), VertexNumericType::Float)
+                        | (ScalarType::Signed(_), VertexNumericType::Signed)
+                        | (ScalarType::Unsigned(_), VertexNumericType::Unsigned)
+                );
+
+                if !is_compatible {
+                    warn!(
+                        "vertex input location {location} ({name:?}) of type {scalar_ty:?} is not \
+                        compatible with {:?}",
+                        attribute.format
+                    );
+
+                    return Err(DriverError::InvalidData);
+                }
             }
         }
 
+        Ok(())
+    }
+
+    /// Returns the vertex input layout of this shader: either the explicit vertex layout or a
+    /// tightly packed, per-vertex layout reflected from the shader code.
+    ///
+    /// Reflected inputs may be 8, 16, 32, or 64-bit scalars or vectors; other input types, such as
+    /// matrices, require an explicit vertex layout (see [`Shader::vertex_layout`]).
+    pub fn vertex_input(&self) -> Result<VertexInputState, DriverError> {
+        if let Some(vertex_layout) = &self.vertex_layout {
+            return Ok(vertex_layout.clone());
+        }
+
+        fn scalar_format(ty: &ScalarType, components: u32) -> Option<vk::Format> {
+            use vk::Format as F;
+
+            const FLOAT16: [F; 4] = [
+                F::R16_SFLOAT,
+                F::R16G16_SFLOAT,
+                F::R16G16B16_SFLOAT,
+                F::R16G16B16A16_SFLOAT,
+            ];
+            const FLOAT32: [F; 4] = [
+                F::R32_SFLOAT,
+                F::R32G32_SFLOAT,
+                F::R32G32B32_SFLOAT,
+                F::R32G32B32A32_SFLOAT,
+            ];
+            const FLOAT64: [F; 4] = [
+                F::R64_SFLOAT,
+                F::R64G64_SFLOAT,
+                F::R64G64B64_SFLOAT,
+                F::R64G64B64A64_SFLOAT,
+            ];
+            const SINT8: [F; 4] = [F::R8_SINT, F::R8G8_SINT, F::R8G8B8_SINT, F::R8G8B8A8_SINT];
+            const SINT16: [F; 4] = [
+                F::R16_SINT,
+                F::R16G16_SINT,
+                F::R16G16B16_SINT,
+                F::R16G16B16A16_SINT,
+            ];
+            const SINT32: [F; 4] = [
+                F::R32_SINT,
+                F::R32G32_SINT,
+                F::R32G32B32_SINT,
+                F::R32G32B32A32_SINT,
+            ];
+            const SINT64: [F; 4] = [
+                F::R64_SINT,
+                F::R64G64_SINT,
+                F::R64G64B64_SINT,
+                F::R64G64B64A64_SINT,
+            ];
+            const UINT8: [F; 4] = [F::R8_UINT, F::R8G8_UINT, F::R8G8B8_UINT, F::R8G8B8A8_UINT];
+            const UINT16: [F; 4] = [
+                F::R16_UINT,
+                F::R16G16_UINT,
+                F::R16G16B16_UINT,
+                F::R16G16B16A16_UINT,
+            ];
+            const UINT32: [F; 4] = [
+                F::R32_UINT,
+                F::R32G32_UINT,
+                F::R32G32B32_UINT,
+                F::R32G32B32A32_UINT,
+            ];
+            const UINT64: [F; 4] = [
+                F::R64_UINT,
+                F::R64G64_UINT,
+                F::R64G64B64_UINT,
+                F::R64G64B64A64_UINT,
+            ];
+
+            let formats = match ty {
+                ScalarType::Float(2) => &FLOAT16,
+                ScalarType::Float(4) => &FLOAT32,
+                ScalarType::Float(8) => &FLOAT64,
+                ScalarType::Signed(1) => &SINT8,
+                ScalarType::Signed(2) => &SINT16,
+                ScalarType::Signed(4) => &SINT32,
+                ScalarType::Signed(8) => &SINT64,
+                ScalarType::Unsigned(1) => &UINT8,
+                ScalarType::Unsigned(2) => &UINT16,
+                ScalarType::Unsigned(4) => &UINT32,
+                ScalarType::Unsigned(8) => &UINT64,
+                _ => return None,
+            };
+
+            formats.get(components.checked_sub(1)? as usize).copied()
+        }
+
         let mut input_rates_strides = HashMap::new();
         let mut vertex_attribute_descriptions = vec![];
 
@@ -653,7 +1293,14 @@ impl Shader {
                 .unwrap_or_default();
             let (location, _) = location.into_inner();
             if let Some((input_rate, _)) = input_rates_strides.get(&binding) {
-                assert_eq!(*input_rate, guessed_rate);
+                if *input_rate != guessed_rate {
+                    warn!(
+                        "vertex input location {location} ({name:?}) uses binding {binding} with \
+                        both vertex and instance rates"
+                    );
+
+                    return Err(DriverError::InvalidData);
+                }
             }
 
             let byte_stride = ty.nbyte().unwrap_or_default() as u32;
@@ -667,10 +1314,18 @@ impl Shader {
                 location,
                 binding,
                 format: match ty {
-                    Type::Scalar(ty) => scalar_format(ty, ty.nbyte() as _),
-                    Type::Vector(ty) => scalar_format(&ty.scalar_ty, byte_stride),
-                    _ => unimplemented!("{:?}", ty),
-                },
+                    Type::Scalar(ty) => scalar_format(ty, 1),
+                    Type::Vector(ty) => scalar_format(&ty.scalar_ty, ty.nscalar),
+                    _ => None,
+                }
+                .ok_or_else(|| {
+                    warn!(
+                        "unsupported vertex input location {location} ({name:?}) of type {ty:?}; \
+                        use an explicit vertex layout"
+                    );
+
+                    DriverError::Unsupported
+                })?,
                 offset: byte_stride, // Figured out below - this data is iter'd in an unknown order
             });
         }
@@ -715,10 +1370,10 @@ impl Shader {
             });
         }
 
-        VertexInputState {
+        Ok(VertexInputState {
             vertex_attribute_descriptions,
             vertex_binding_descriptions,
-        }
+        })
     }
 }
 
@@ -728,6 +1383,37 @@ impl ShaderBuilder {
         Self::default().stage(stage).spirv(spirv)
     }
 
+    /// Specifies the sampler used by a combined image sampler binding, instead of the sampler
+    /// guessed from the binding name.
+    pub fn image_sampler(
+        mut self,
+        descriptor_binding: DescriptorBinding,
+        info: impl Into<SamplerInfo>,
+    ) -> Self {
+        self.image_samplers
+            .get_or_insert_with(Default::default)
+            .insert(descriptor_binding, info.into());
+        self
+    }
+
+    /// Sets the value of a specialization constant, found using its name in the shader code.
+    ///
+    /// _NOTE:_ Panics if the constant is not found or if the type of `value` does not match the
+    /// declared type of the constant.
+    pub fn specialize(mut self, name: &str, value: impl Into<SpecializationValue>) -> Self {
+        self.specialization_info = Some(Some(
+            SpecializationInfo::specialize_named(
+                self.entry_name.as_deref().unwrap_or("main"),
+                self.spirv.as_deref().unwrap(),
+                self.specialization_info.flatten(),
+                name,
+                value.into(),
+            )
+            .expect("invalid specialization constant"),
+        ));
+        self
+    }
+
     pub fn build(mut self) -> Shader {
         self.entry_point = Some(
             Shader::reflect_entry_point(
@@ -801,7 +1487,7 @@ impl ShaderCode for Vec<u32> {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct SpecializationInfo {
     pub data: Vec<u8>,
     pub map_entries: Vec<vk::SpecializationMapEntry>,
@@ -817,4 +1503,163 @@ impl SpecializationInfo {
             map_entries: map_entries.into(),
         }
     }
+
+    /// Sets the value of the specialization constant with the given constant ID.
+    ///
+    /// Map entries are kept in constant ID order so that equal values produce equal data.
+    pub fn set(&mut self, constant_id: u32, value: impl Into<SpecializationValue>) {
+        let mut constants = self
+            .map_entries
+            .iter()
+            .filter(|entry| entry.constant_id != constant_id)
+            .map(|entry| {
+                let start = entry.offset as usize;
+
+                (
+                    entry.constant_id,
+                    self.data[start..start + entry.size].to_vec(),
+                )
+            })
+            .collect::<Vec<_>>();
+        constants.push((constant_id, value.into().to_ne_bytes()));
+        constants.sort_unstable_by_key(|(constant_id, _)| *constant_id);
+
+        self.data.clear();
+        self.map_entries.clear();
+
+        for (constant_id, data) in constants {
+            self.map_entries.push(vk::SpecializationMapEntry {
+                constant_id,
+                offset: self.data.len() as _,
+                size: data.len(),
+            });
+            self.data.extend_from_slice(&data);
+        }
+    }
+
+    pub(super) fn set_named(
+        &mut self,
+        spec_constants: &SpecializationConstantMap,
+        name: &str,
+        value: SpecializationValue,
+    ) -> Result<(), DriverError> {
+        let (constant_id, ty) = spec_constants.get(name).ok_or_else(|| {
+            warn!("specialization constant `{name}` not found");
+
+            DriverError::InvalidData
+        })?;
+
+        if !value.is_compatible(ty) {
+            warn!(
+                "specialization constant `{name}` of type {ty:?} is not compatible with {value:?}"
+            );
+
+            return Err(DriverError::InvalidData);
+        }
+
+        self.set(*constant_id, value);
+
+        Ok(())
+    }
+
+    /// Returns the given specialization info with a named constant of the given shader code set.
+    pub(super) fn specialize_named(
+        entry_name: &str,
+        spirv: &[u8],
+        specialization_info: Option<Self>,
+        name: &str,
+        value: SpecializationValue,
+    ) -> Result<Self, DriverError> {
+        let entry_point = Shader::reflect_entry_point(entry_name, spirv, None)?;
+        let mut specialization_info = specialization_info.unwrap_or_default();
+        specialization_info.set_named(&spec_constants(&entry_point), name, value)?;
+
+        Ok(specialization_info)
+    }
+
+    fn key(&self) -> impl Iterator<Item = (u32, u32, usize)> + '_ {
+        self.map_entries
+            .iter()
+            .map(|entry| (entry.constant_id, entry.offset, entry.size))
+    }
+}
+
+impl Eq for SpecializationInfo {}
+
+impl Hash for SpecializationInfo {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data.hash(state);
+
+        for entry in self.key() {
+            entry.hash(state);
+        }
+    }
+}
+
+impl PartialEq for SpecializationInfo {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data && self.key().eq(other.key())
+    }
+}
+
+/// The value of a specialization constant.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpecializationValue {
+    Bool(bool),
+    F32(f32),
+    F64(f64),
+    I32(i32),
+    I64(i64),
+    U32(u32),
+    U64(u64),
 }
+
+impl SpecializationValue {
+    fn is_compatible(self, ty: &Type) -> bool {
+        let ty = match ty {
+            Type::Scalar(ty) => ty,
+            _ => return false,
+        };
+
+        matches!(
+            (self, ty),
+            (Self::Bool(_), ScalarType::Boolean)
+                | (Self::F32(_), ScalarType::Float(4))
+                | (Self::F64(_), ScalarType::Float(8))
+                | (Self::I32(_), ScalarType::Signed(4))
+                | (Self::I64(_), ScalarType::Signed(8))
+                | (Self::U32(_), ScalarType::Unsigned(4))
+                | (Self::U64(_), ScalarType::Unsigned(8))
+        )
+    }
+
+    fn to_ne_bytes(self) -> Vec<u8> {
+        match self {
+            Self::Bool(value) => (value as vk::Bool32).to_ne_bytes().to_vec(),
+            Self::F32(value) => value.to_ne_bytes().to_vec(),
+            Self::F64(value) => value.to_ne_bytes().to_vec(),
+            Self::I32(value) => value.to_ne_bytes().to_vec(),
+            Self::I64(value) => value.to_ne_bytes().to_vec(),
+            Self::U32(value) => value.to_ne_bytes().to_vec(),
+            Self::U64(value) => value.to_ne_bytes().to_vec(),
+        }
+    }
+}
+
+macro_rules! specialization_value {
+    ($ty:ty, $variant:ident) => {
+        impl From<$ty> for SpecializationValue {
+            fn from(value: $ty) -> Self {
+                Self::$variant(value)
+            }
+        }
+    };
+}
+
+specialization_value!(bool, Bool);
+specialization_value!(f32, F32);
+specialization_value!(f64, F64);
+specialization_value!(i32, I32);
+specialization_value!(i64, I64);
+specialization_value!(u32, U32);
+specialization_value!(u64, U64);
@@ -17,6 +17,7 @@ where
     pub info: SwapchainInfo,
     next_semaphore: usize,
     acquired_semaphores: Vec<vk::Semaphore>,
+    present_mode: vk::PresentModeKHR,
     rendered_semaphores: Vec<vk::Semaphore>, // TODO: make a single semaphore
     suboptimal: bool,
     surface: Surface<P>,
@@ -53,6 +54,7 @@ where
             info,
             next_semaphore: 0,
             acquired_semaphores,
+            present_mode: vk::PresentModeKHR::FIFO,
             rendered_semaphores,
             suboptimal: true,
             surface,
@@ -60,6 +62,11 @@ where
         })
     }
 
+    /// Returns the format and color space of the swapchain images.
+    pub fn format(&self) -> vk::SurfaceFormatKHR {
+        self.info.format
+    }
+
     pub fn acquire_next_image(&mut self) -> Result<SwapchainImage<P>, SwapchainError> {
         if self.suboptimal {
             self.recreate_swapchain()
@@ -185,6 +192,59 @@ where
         self.images[image.idx as usize] = Some(image.image);
     }
 
+    /// Returns the requested image usage, less any usage the surface or format does not support.
+    fn image_usage(&self, supported_usage: vk::ImageUsageFlags) -> vk::ImageUsageFlags {
+        let format_features = unsafe {
+            self.device.instance.get_physical_device_format_properties(
+                *self.device.physical_device,
+                self.info.format.format,
+            )
+        }
+        .optimal_tiling_features;
+        let mut usage = self.info.image_usage & supported_usage;
+
+        for (image_usage, format_feature) in [
+            (
+                vk::ImageUsageFlags::COLOR_ATTACHMENT,
+                vk::FormatFeatureFlags::COLOR_ATTACHMENT,
+            ),
+            (
+                vk::ImageUsageFlags::SAMPLED,
+                vk::FormatFeatureFlags::SAMPLED_IMAGE,
+            ),
+            (
+                vk::ImageUsageFlags::STORAGE,
+                vk::FormatFeatureFlags::STORAGE_IMAGE,
+            ),
+            (
+                vk::ImageUsageFlags::TRANSFER_DST,
+                vk::FormatFeatureFlags::TRANSFER_DST,
+            ),
+            (
+                vk::ImageUsageFlags::TRANSFER_SRC,
+                vk::FormatFeatureFlags::TRANSFER_SRC,
+            ),
+        ] {
+            if usage.contains(image_usage) && !format_features.contains(format_feature) {
+                usage &= !image_usage;
+            }
+        }
+
+        if usage != self.info.image_usage {
+            debug!(
+                "Unsupported swapchain image usage: {:?}",
+                self.info.image_usage & !usage
+            );
+        }
+
+        usage
+    }
+
+    /// Returns the active presentation mode.
+    pub fn present_mode(&self) -> vk::PresentModeKHR {
+        self.present_mode
+    }
+
     fn recreate_swapchain(&mut self) -> Result<(), DriverError> {
         let res = unsafe { self.device.device_wait_idle() };
 
@@ -241,26 +301,13 @@ where
             return Err(DriverError::Unsupported);
         }
 
-        let present_mode_preference = if self.info.sync_display {
-            vec![vk::PresentModeKHR::FIFO_RELAXED, vk::PresentModeKHR::FIFO]
-        } else {
-            vec![vk::PresentModeKHR::MAILBOX, vk::PresentModeKHR::IMMEDIATE]
-        };
-
-        let present_modes = unsafe {
-            surface_ext.get_physical_device_surface_present_modes(
-                *self.device.physical_device,
-                *self.surface,
-            )
-        }
-        .map_err(|err| {
-            warn!("{err}");
-
-            DriverError::Unsupported
-        })?;
-
-        let present_mode = present_mode_preference
-            .into_iter()
+        // FIFO is the only mode which is required to be supported
+        let present_modes = self.supported_present_modes()?;
+        let present_mode = self
+            .info
+            .present_modes
+            .iter()
+            .copied()
             .find(|mode| present_modes.contains(mode))
             .unwrap_or(vk::PresentModeKHR::FIFO);
 
@@ -275,7 +322,7 @@ where
             surface_capabilities.current_transform
         };
 
-        // info!("supported_usage_flags {:#?}", &surface_capabilities.supported_usage_flags);
+        let image_usage = self.image_usage(surface_capabilities.supported_usage_flags);
 
         let swapchain_create_info = vk::SwapchainCreateInfoKHR::builder()
             .surface(*self.surface)
@@ -286,7 +333,7 @@ where
                 width: surface_width,
                 height: surface_height,
             })
-            .image_usage(surface_capabilities.supported_usage_flags)
+            .image_usage(image_usage)
             .image_sharing_mode(vk::SharingMode::EXCLUSIVE)
             .pre_transform(pre_transform)
             .composite_alpha(vk::CompositeAlphaFlagsKHR::OPAQUE)
@@ -312,14 +359,10 @@ where
                     vk_image,
                     ImageInfo {
                         ty: ImageType::Texture2D,
-                        usage: vk::ImageUsageFlags::COLOR_ATTACHMENT
-                            | vk::ImageUsageFlags::SAMPLED
-                            | vk::ImageUsageFlags::STORAGE // TODO: Conditionally enable! When SRGB is supported for it!
-                            | vk::ImageUsageFlags::TRANSFER_DST
-                            | vk::ImageUsageFlags::TRANSFER_SRC,
+                        usage: image_usage,
                         flags: vk::ImageCreateFlags::empty(), // MUTABLE_FORMAT | SPARSE_ALIASED | CUBE_COMPATIBLE
-                        fmt: vk::Format::B8G8R8A8_UNORM,      // TODO: Allow configuration!
-                        depth: 0,                             // TODO: 1?
+                        fmt: self.info.format.format,
+                        depth: 0, // TODO: 1?
                         height: surface_height,
                         width: surface_width,
                         sample_count: SampleCount::X1,
@@ -339,10 +382,37 @@ where
         self.info.width = surface_width;
         self.next_semaphore = 0;
         self.images = images;
+        self.present_mode = present_mode;
         self.swapchain = swapchain;
 
         Ok(())
     }
+
+    /// Sets the presentation modes, in order of preference, and recreates the swapchain when the
+    /// next image is acquired.
+    pub fn set_present_modes(&mut self, present_modes: impl Into<Vec<vk::PresentModeKHR>>) {
+        self.info.present_modes = present_modes.into();
+        self.suboptimal = true;
+    }
+
+    /// Returns the presentation modes supported by the surface.
+    pub fn supported_present_modes(&self) -> Result<Vec<vk::PresentModeKHR>, DriverError> {
+        unsafe {
+            self.device
+                .surface_ext
+                .as_ref()
+                .unwrap()
+                .get_physical_device_surface_present_modes(
+                    *self.device.physical_device,
+                    *self.surface,
+                )
+        }
+        .map_err(|err| {
+            warn!("{err}");
+
+            DriverError::Unsupported
+        })
+    }
 }
 
 impl<P> Drop for Swapchain<P>
@@ -414,13 +484,98 @@ pub enum SwapchainError {
     SurfaceLost,
 }
 
-#[derive(Builder, Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// A kind of surface format and color space which may be used by swapchain images, in order of
+/// preference using `DriverConfig::surface_formats`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SurfaceFormatPreference {
+    /// A specific format and color space.
+    Exact(vk::SurfaceFormatKHR),
+
+    /// 16-bit floating point scRGB with linear encoding, where values outside of `0..=1` extend
+    /// the sRGB gamut and brightness.
+    ///
+    /// _NOTE:_ Requires `VK_EXT_swapchain_colorspace`.
+    ExtendedSrgbLinear,
+
+    /// 10-bit HDR10 with SMPTE ST 2084 (PQ) encoding and BT.2020 primaries; shaders must encode
+    /// their output.
+    ///
+    /// _NOTE:_ Requires `VK_EXT_swapchain_colorspace`.
+    Hdr10,
+
+    /// 8-bit sRGB formats, which encode shader output to sRGB when written.
+    Srgb,
+
+    /// 10-bit UNORM formats using the sRGB color space; shaders must encode their output.
+    TenBit,
+
+    /// 8-bit UNORM formats using the sRGB color space; shaders must encode their output.
+    Unorm,
+}
+
+impl SurfaceFormatPreference {
+    /// Returns `true` if the given surface format is of this kind.
+    pub fn is_match(self, format: vk::SurfaceFormatKHR) -> bool {
+        use vk::{ColorSpaceKHR as C, Format as F};
+
+        match self {
+            Self::Exact(exact) => exact == format,
+            Self::ExtendedSrgbLinear => {
+                format.color_space == C::EXTENDED_SRGB_LINEAR_EXT
+                    && format.format == F::R16G16B16A16_SFLOAT
+            }
+            Self::Hdr10 => {
+                format.color_space == C::HDR10_ST2084_EXT
+                    && matches!(
+                        format.format,
+                        F::A2B10G10R10_UNORM_PACK32 | F::A2R10G10B10_UNORM_PACK32
+                    )
+            }
+            Self::Srgb => {
+                format.color_space == C::SRGB_NONLINEAR
+                    && matches!(
+                        format.format,
+                        F::A8B8G8R8_SRGB_PACK32 | F::B8G8R8A8_SRGB | F::R8G8B8A8_SRGB
+                    )
+            }
+            Self::TenBit => {
+                format.color_space == C::SRGB_NONLINEAR
+                    && matches!(
+                        format.format,
+                        F::A2B10G10R10_UNORM_PACK32 | F::A2R10G10B10_UNORM_PACK32
+                    )
+            }
+            Self::Unorm => {
+                format.color_space == C::SRGB_NONLINEAR
+                    && matches!(
+                        format.format,
+                        F::A8B8G8R8_UNORM_PACK32 | F::B8G8R8A8_UNORM | F::R8G8B8A8_UNORM
+                    )
+            }
+        }
+    }
+}
+
+impl From<vk::SurfaceFormatKHR> for SurfaceFormatPreference {
+    fn from(format: vk::SurfaceFormatKHR) -> Self {
+        Self::Exact(format)
+    }
+}
+
+#[derive(Builder, Clone, Debug, Eq, Hash, PartialEq)]
 #[builder(pattern = "owned")]
 pub struct SwapchainInfo {
     pub desired_image_count: u32,
     pub format: vk::SurfaceFormatKHR,
     pub height: u32,
-    pub sync_display: bool,
+
+    /// The requested usage of swapchain images; usage which is not supported by the surface or
+    /// format is removed when the swapchain is created.
+    pub image_usage: vk::ImageUsageFlags,
+
+    /// Presentation modes in order of preference; `FIFO` is used if none are supported.
+    pub present_modes: Vec<vk::PresentModeKHR>,
+
     pub width: u32,
 }
 
@@ -0,0 +1,148 @@
+use {
+    parking_lot::{Condvar, Mutex},
+    std::{
+        fmt::{Debug, Formatter},
+        panic::{catch_unwind, AssertUnwindSafe},
+        sync::{
+            mpsc::{channel, Receiver, Sender},
+            Arc,
+        },
+        thread::{available_parallelism, Builder, JoinHandle},
+    },
+};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// The largest number of worker threads of a device.
+const MAX_THREAD_COUNT: usize = 4;
+
+/// The result of a job which has been submitted to a [`WorkerPool`].
+pub(super) struct JobHandle<T> {
+    state: Arc<(Mutex<Option<Option<T>>>, Condvar)>,
+}
+
+impl<T> JobHandle<T> {
+    pub fn is_finished(&self) -> bool {
+        self.state.0.lock().is_some()
+    }
+
+    /// Blocks until the job has finished, returning `None` if it panicked.
+    pub fn wait(self) -> Option<T> {
+        let (result, finished) = &*self.state;
+        let mut result = result.lock();
+
+        while result.is_none() {
+            finished.wait(&mut result);
+        }
+
+        result.take().unwrap()
+    }
+}
+
+impl<T> Debug for JobHandle<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobHandle")
+            .field("is_finished", &self.is_finished())
+            .finish()
+    }
+}
+
+/// A small, fixed number of threads which run jobs, such as pipeline compilation, in the order
+/// they were submitted.
+///
+/// Threads are started when jobs are first submitted.
+pub(super) struct WorkerPool {
+    receiver: Arc<Mutex<Receiver<Job>>>,
+    sender: Mutex<Option<Sender<Job>>>,
+    threads: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl WorkerPool {
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+
+        Self {
+            receiver: Arc::new(Mutex::new(receiver)),
+            sender: Mutex::new(Some(sender)),
+            threads: Default::default(),
+        }
+    }
+
+    /// Stops accepting jobs and waits for all submitted jobs to finish.
+    pub fn join(&self) {
+        self.sender.lock().take();
+
+        for thread in self.threads.lock().drain(..) {
+            thread.join().ok();
+        }
+    }
+
+    fn start_thread(&self) {
+        let mut threads = self.threads.lock();
+        let thread_count = available_parallelism()
+            .map(|count| count.get().saturating_sub(1))
+            .unwrap_or(1)
+            .clamp(1, MAX_THREAD_COUNT);
+
+        if threads.len() >= thread_count {
+            return;
+        }
+
+        let receiver = Arc::clone(&self.receiver);
+        let thread = Builder::new()
+            .name(format!("screen-13 worker {}", threads.len()))
+            .spawn(move || loop {
+                // The lock is released before the job runs so that other threads may receive jobs
+                let job = receiver.lock().recv();
+
+                match job {
+                    Ok(job) => job(),
+                    Err(_) => break,
+                }
+            });
+
+        if let Ok(thread) = thread {
+            threads.push(thread);
+        }
+    }
+
+    /// Runs a job on a worker thread.
+    pub fn submit<T>(&self, job: impl FnOnce() -> T + Send + 'static) -> JobHandle<T>
+    where
+        T: Send + 'static,
+    {
+        let state = Arc::new((Mutex::new(None), Condvar::new()));
+        let handle = JobHandle {
+            state: Arc::clone(&state),
+        };
+        let job: Job = Box::new(move || {
+            let result = catch_unwind(AssertUnwindSafe(job)).ok();
+            let (state, finished) = &*state;
+
+            *state.lock() = Some(result);
+            finished.notify_all();
+        });
+
+        self.start_thread();
+
+        // Jobs submitted after the pool has been joined run on the calling thread
+        let job = match self.sender.lock().as_ref() {
+            Some(sender) => sender.send(job).err().map(|err| err.0),
+            None => Some(job),
+        };
+
+        if let Some(job) = job {
+            job();
+        }
+
+        handle
+    }
+}
+
+impl Debug for WorkerPool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkerPool")
+            .field("threads", &self.threads.lock().len())
+            .finish()
+    }
+}
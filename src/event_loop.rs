@@ -1,10 +1,12 @@
 use {
     super::{
+        capture::{CaptureSequence, Captures},
         driver::{Device, Driver, DriverConfigBuilder, DriverError},
         frame::FrameContext,
         Display, DisplayError,
     },
     archery::{ArcK, SharedPointer, SharedPointerKind},
+    ash::vk,
     log::{debug, info, trace, warn},
     std::{
         marker::PhantomData,
@@ -41,6 +43,7 @@ pub struct EventLoop<P>
 where
     P: SharedPointerKind + Send,
 {
+    captures: Captures<P>,
     pub device: SharedPointer<Device<P>, P>,
     display: Display<P>,
     event_loop: winit::event_loop::EventLoop<()>,
@@ -66,6 +69,7 @@ where
         P: 'static,
     {
         let mut events = Vec::new();
+        let mut present_modes = None;
         let mut will_exit = false;
 
         // Use the same delta-time smoothing as Kajiya; but start it off with a reasonable
@@ -131,15 +135,21 @@ where
             };
 
             let (swapchain, mut render_graph) = self.display.acquire_next_image()?;
+            let dt = self.captures.dt().unwrap_or(dt_filtered);
+            let present_mode = self.display.present_mode();
+            let (width, height) = (self.width(), self.height());
 
             frame_fn(FrameContext {
+                captures: &mut self.captures,
                 device: &self.device,
-                dt: dt_filtered,
-                height: self.height(),
+                dt,
+                height,
                 render_graph: &mut render_graph,
                 events: take(&mut events).as_slice(),
+                present_mode,
+                present_modes: &mut present_modes,
                 swapchain_image: swapchain,
-                width: self.width(),
+                width,
                 window: &self.window,
                 will_exit: &mut will_exit,
             });
@@ -152,12 +162,38 @@ where
                 ((elapsed.as_secs_f32() / refresh_rate) * 100.0) as usize,
             );
 
+            self.captures.push_sequence(&mut render_graph, swapchain)?;
             self.display.present_image(render_graph, swapchain)?;
+            self.captures.update();
+
+            if let Some(present_modes) = present_modes.take() {
+                self.display.set_present_modes(present_modes);
+            }
         }
 
+        // Deliver any captures still in flight
+        self.display.wait_idle()?;
+        self.captures.update();
+
         Ok(())
     }
 
+    /// Returns the active presentation mode of the swapchain.
+    pub fn present_mode(&self) -> vk::PresentModeKHR {
+        self.display.present_mode()
+    }
+
+    /// Returns the format and color space of the swapchain images, which shaders writing to the
+    /// swapchain may need in order to encode their output.
+    pub fn swapchain_format(&self) -> vk::SurfaceFormatKHR {
+        self.display.swapchain_format()
+    }
+
+    /// Returns the presentation modes supported by the surface.
+    pub fn supported_present_modes(&self) -> Result<Vec<vk::PresentModeKHR>, DriverError> {
+        self.display.supported_present_modes()
+    }
+
     pub fn width(&self) -> u32 {
         self.window.inner_size().width
     }
@@ -170,6 +206,7 @@ where
 #[derive(Debug)]
 pub struct EventLoopBuilder<P> {
     __: PhantomData<P>,
+    capture_sequence: Option<CaptureSequence>,
     driver_cfg: DriverConfigBuilder,
     event_loop: winit::event_loop::EventLoop<()>,
     window: WindowBuilder,
@@ -179,6 +216,7 @@ impl<P> Default for EventLoopBuilder<P> {
     fn default() -> Self {
         Self {
             __: PhantomData,
+            capture_sequence: None,
             driver_cfg: DriverConfigBuilder::default(),
             event_loop: winit::event_loop::EventLoop::new(),
             window: Default::default(),
@@ -192,6 +230,13 @@ impl<P> EventLoopBuilder<P> {
         self.event_loop.available_monitors()
     }
 
+    /// Records every presented frame, using a fixed time step instead of the measured frame
+    /// time.
+    pub fn capture_sequence(mut self, capture_sequence: CaptureSequence) -> Self {
+        self.capture_sequence = Some(capture_sequence);
+        self
+    }
+
     pub fn configure<ConfigureFn>(mut self, configure_fn: ConfigureFn) -> Self
     where
         ConfigureFn: FnOnce(DriverConfigBuilder) -> DriverConfigBuilder,
@@ -207,8 +252,9 @@ impl<P> EventLoopBuilder<P> {
         self
     }
 
-    pub fn sync_display(mut self, sync_display: bool) -> Self {
-        self.driver_cfg = self.driver_cfg.sync_display(sync_display);
+    /// Sets the presentation modes, in order of preference; see `DriverConfig::present_modes`.
+    pub fn present_modes(mut self, present_modes: impl Into<Vec<vk::PresentModeKHR>>) -> Self {
+        self.driver_cfg = self.driver_cfg.present_modes(present_modes.into());
         self
     }
 
@@ -308,6 +354,7 @@ where
         );
 
         Ok(EventLoop {
+            captures: Captures::new(&driver.device, self.capture_sequence),
             device: SharedPointer::clone(&driver.device),
             display,
             event_loop: self.event_loop,
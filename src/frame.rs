@@ -1,9 +1,12 @@
 use {
     crate::{
-        driver::Device,
-        graph::{RenderGraph, SwapchainImageNode},
+        capture::{CaptureImage, Captures},
+        driver::{Device, DriverError},
+        graph::{AnyImageNode, RenderGraph, SwapchainImageNode},
     },
     archery::{SharedPointer, SharedPointerKind},
+    ash::vk,
+    std::path::PathBuf,
     winit::{dpi::PhysicalPosition, event::Event, window::Window},
 };
 
@@ -23,10 +26,20 @@ pub struct FrameContext<'a, P>
 where
     P: SharedPointerKind,
 {
+    pub(super) captures: &'a mut Captures<P>,
     pub device: &'a SharedPointer<Device<P>, P>,
+
+    /// The smoothed frame time, in seconds, or the fixed time step while recording a
+    /// [`CaptureSequence`](crate::CaptureSequence).
     pub dt: f32,
+
     pub events: &'a [Event<'a, ()>],
     pub height: u32,
+
+    /// The active presentation mode of the swapchain.
+    pub present_mode: vk::PresentModeKHR,
+
+    pub(super) present_modes: &'a mut Option<Vec<vk::PresentModeKHR>>,
     pub render_graph: &'a mut RenderGraph<P>,
     pub swapchain_image: SwapchainImageNode<P>,
     pub will_exit: &'a mut bool,
@@ -38,6 +51,46 @@ impl<P> FrameContext<'_, P>
 where
     P: SharedPointerKind,
 {
+    /// Queues a copy of an image, such as `swapchain_image`, as it is at this point in the render
+    /// graph and saves it as a PNG file once the GPU has finished the frame.
+    ///
+    /// Images are converted to 8-bit RGBA: `BGRA` images are swizzled, 10-bit images are rounded
+    /// down, and float images are clamped to `0.0..=1.0` without any color space conversion.
+    ///
+    /// _NOTE:_ Only `R8G8B8A8_*`, `A8B8G8R8_*_PACK32`, `B8G8R8A8_*`, `A2B10G10R10_UNORM_PACK32`,
+    /// `A2R10G10B10_UNORM_PACK32`, `R16G16B16A16_SFLOAT`, and `R32G32B32A32_SFLOAT` formats are
+    /// supported. Returns `DriverError::Unsupported` if the image was not created with
+    /// `TRANSFER_SRC` usage, which swapchain images may not support on some surfaces.
+    pub fn capture(
+        &mut self,
+        image: impl Into<AnyImageNode<P>>,
+        path: impl Into<PathBuf>,
+    ) -> Result<(), DriverError>
+    where
+        P: Send + 'static,
+    {
+        self.captures
+            .push_png(self.render_graph, image, path.into())
+    }
+
+    /// Queues a copy of an image, such as `swapchain_image`, as it is at this point in the render
+    /// graph and calls `func` with the RGBA8 pixels once the GPU has finished the frame.
+    ///
+    /// Captures are delivered in the order they were queued, on the thread running the event
+    /// loop, during a later frame or before the event loop returns.
+    ///
+    /// _NOTE:_ See [`FrameContext::capture`] for the supported image formats.
+    pub fn capture_rgba8(
+        &mut self,
+        image: impl Into<AnyImageNode<P>>,
+        func: impl FnOnce(CaptureImage) + 'static,
+    ) -> Result<(), DriverError>
+    where
+        P: Send + 'static,
+    {
+        self.captures.push_func(self.render_graph, image, func)
+    }
+
     pub fn exit(&mut self) {
         *self.will_exit = true;
     }
@@ -46,6 +99,12 @@ where
         self.width as f32 / self.height as f32
     }
 
+    /// Sets the presentation modes, in order of preference, after this frame has been presented;
+    /// the swapchain is recreated using the most preferred supported mode, or `FIFO`.
+    pub fn set_present_modes(&mut self, present_modes: impl Into<Vec<vk::PresentModeKHR>>) {
+        *self.present_modes = Some(present_modes.into());
+    }
+
     pub fn center_cursor(&self) {
         center_cursor(self.window);
     }
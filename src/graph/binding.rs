@@ -1,11 +1,12 @@
 use {
     super::{
         AccelerationStructureLeaseNode, AccelerationStructureNode, BufferLeaseNode, BufferNode,
-        ImageLeaseNode, ImageNode, RenderGraph, SwapchainImageBinding,
+        ImageLeaseNode, ImageNode, RenderGraph, SamplerNode, SwapchainImageBinding,
     },
     crate::{
         driver::{
             AccelerationStructure, AccelerationStructureInfo, Buffer, BufferInfo, Image, ImageInfo,
+            Sampler, SamplerInfo,
         },
         Lease,
     },
@@ -119,6 +120,7 @@ where
     BufferLease(BufferLeaseBinding<P>, bool),
     Image(ImageBinding<P>, bool),
     ImageLease(ImageLeaseBinding<P>, bool),
+    Sampler(SamplerBinding<P>, bool),
     SwapchainImage(SwapchainImageBinding<P>, bool),
 }
 
@@ -134,6 +136,7 @@ where
             Self::BufferLease(binding, _) => binding.access_mut(access),
             Self::Image(binding, _) => binding.access_mut(access),
             Self::ImageLease(binding, _) => binding.access_mut(access),
+            Self::Sampler(binding, _) => binding.access_mut(access),
             Self::SwapchainImage(binding, _) => binding.access_mut(access),
         }
     }
@@ -163,6 +166,13 @@ where
         })
     }
 
+    pub(super) fn as_driver_sampler(&self) -> Option<&Sampler<P>> {
+        Some(match self {
+            Self::Sampler(binding, _) => &binding.item,
+            _ => return None,
+        })
+    }
+
     pub(super) fn image_info(&self) -> Option<ImageInfo> {
         Some(match self {
             Self::Image(binding, _) => binding.item.info,
@@ -180,6 +190,7 @@ where
             Self::BufferLease(_, is_bound) => *is_bound,
             Self::Image(_, is_bound) => *is_bound,
             Self::ImageLease(_, is_bound) => *is_bound,
+            Self::Sampler(_, is_bound) => *is_bound,
             Self::SwapchainImage(_, is_bound) => *is_bound,
         }
     }
@@ -192,6 +203,7 @@ where
             Self::BufferLease(_, is_bound) => is_bound,
             Self::Image(_, is_bound) => is_bound,
             Self::ImageLease(_, is_bound) => is_bound,
+            Self::Sampler(_, is_bound) => is_bound,
             Self::SwapchainImage(_, is_bound) => is_bound,
         } = false;
     }
@@ -317,6 +329,7 @@ macro_rules! bind {
 bind!(AccelerationStructure);
 bind!(Image);
 bind!(Buffer);
+bind!(Sampler);
 
 macro_rules! bind_lease {
     ($name:ident) => {
@@ -465,6 +478,15 @@ where
     }
 }
 
+impl<P> SamplerBinding<P>
+where
+    P: SharedPointerKind,
+{
+    pub fn info(&self) -> &SamplerInfo {
+        &self.item.info
+    }
+}
+
 impl<P> SwapchainImageBinding<P>
 where
     P: SharedPointerKind,
@@ -3,13 +3,13 @@ use {
         AccelerationStructureBinding, AccelerationStructureLeaseBinding,
         AccelerationStructureLeaseNode, AccelerationStructureNode, BufferBinding,
         BufferLeaseBinding, BufferLeaseNode, BufferNode, ImageBinding, ImageLeaseBinding,
-        ImageLeaseNode, ImageNode, PassRef, PipelinePassRef, RenderGraph, Resolver,
-        SwapchainImageBinding, SwapchainImageNode,
+        ImageLeaseNode, ImageNode, PassRef, PipelinePassRef, RenderGraph, Resolver, SamplerBinding,
+        SamplerNode, SwapchainImageBinding, SwapchainImageNode,
     },
     crate::{
         driver::{
             AccelerationStructure, Buffer, ComputePipeline, GraphicPipeline, Image,
-            RayTracePipeline, SwapchainImage,
+            RayTracePipeline, Sampler, SwapchainImage,
         },
         Lease,
     },
@@ -45,6 +45,8 @@ graph_edge!(BufferLeaseBinding -> BufferLeaseNode);
 graph_edge!(Image -> ImageNode);
 graph_edge!(ImageBinding -> ImageNode);
 graph_edge!(ImageLeaseBinding -> ImageLeaseNode);
+graph_edge!(Sampler -> SamplerNode);
+graph_edge!(SamplerBinding -> SamplerNode);
 graph_edge!(SwapchainImage -> SwapchainImageNode);
 graph_edge!(SwapchainImageBinding -> SwapchainImageNode);
 
@@ -55,6 +57,7 @@ graph_edge!(BufferNode -> BufferBinding);
 graph_edge!(BufferLeaseNode -> BufferLeaseBinding);
 graph_edge!(ImageNode -> ImageBinding);
 graph_edge!(ImageLeaseNode -> ImageLeaseBinding);
+graph_edge!(SamplerNode -> SamplerBinding);
 graph_edge!(SwapchainImageNode -> SwapchainImageBinding);
 
 macro_rules! graph_lease_edge {
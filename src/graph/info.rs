@@ -1,9 +1,9 @@
 use {
     super::{
         AccelerationStructureLeaseNode, AccelerationStructureNode, BufferLeaseNode, BufferNode,
-        ImageLeaseNode, ImageNode, RenderGraph, SwapchainImageNode,
+        ImageLeaseNode, ImageNode, RenderGraph, SamplerNode, SwapchainImageNode,
     },
-    crate::driver::{AccelerationStructureInfo, BufferInfo, ImageInfo},
+    crate::driver::{AccelerationStructureInfo, BufferInfo, ImageInfo, SamplerInfo},
     archery::SharedPointerKind,
 };
 
@@ -33,4 +33,5 @@ information!(buffer: BufferNode -> BufferInfo);
 information!(buffer_lease: BufferLeaseNode -> BufferInfo);
 information!(image: ImageNode -> ImageInfo);
 information!(image_lease: ImageLeaseNode -> ImageInfo);
+information!(sampler: SamplerNode -> SamplerInfo);
 information!(swapchain_image: SwapchainImageNode -> ImageInfo);
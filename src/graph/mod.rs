@@ -4,6 +4,7 @@ mod info;
 mod node;
 mod pass_ref;
 mod resolver;
+mod shader_bindings;
 mod swapchain;
 
 pub use {
@@ -11,15 +12,19 @@ pub use {
         binding::{
             AccelerationStructureBinding, AccelerationStructureLeaseBinding, AnyBufferBinding,
             AnyImageBinding, Bind, BufferBinding, BufferLeaseBinding, ImageBinding,
-            ImageLeaseBinding,
+            ImageLeaseBinding, SamplerBinding,
         },
         node::{
             AccelerationStructureLeaseNode, AccelerationStructureNode,
             AnyAccelerationStructureNode, AnyBufferNode, AnyImageNode, BufferLeaseNode, BufferNode,
-            ImageLeaseNode, ImageNode, SwapchainImageNode, Unbind, View, ViewType,
+            ImageLeaseNode, ImageNode, SamplerNode, SwapchainImageNode, Unbind, View, ViewType,
         },
-        pass_ref::{Bindings, Compute, Draw, PassRef, PipelinePassRef, RayTrace},
+        pass_ref::{Access, Bindings, Compute, Draw, PassRef, PipelinePassRef, RayTrace},
         resolver::Resolver,
+        shader_bindings::{
+            DescriptorNode, DescriptorNodeKind, NodeKind, ShaderBindings, ShaderBindingsError,
+            ShaderDescriptors,
+        },
         swapchain::SwapchainImageBinding,
     },
     vk_sync::AccessType,
@@ -30,15 +35,16 @@ use {
     crate::driver::{
         buffer_copy_subresources, buffer_image_copy_subresource, format_aspect_mask,
         is_write_access, BufferSubresource, ComputePipeline, DepthStencilMode,
-        DescriptorBindingMap, Device, GraphicPipeline, ImageSubresource, ImageType,
-        PipelineDescriptorInfo, RayTracePipeline, SampleCount,
+        DescriptorBindingMap, DescriptorNameMap, Device, GraphicPipeline, ImageSubresource,
+        ImageType, PipelineDescriptorInfo, RayTracePipeline, SampleCount, SamplerInfo,
     },
     archery::{SharedPointer, SharedPointerKind},
     ash::vk,
     std::{
         cmp::Ord,
         collections::{BTreeMap, BTreeSet},
-        fmt::{Debug, Formatter},
+        error::Error,
+        fmt::{Debug, Display, Formatter},
         ops::Range,
     },
 };
@@ -294,6 +300,30 @@ impl From<(DescriptorSetIndex, BindingIndex, [BindingOffset; 1])> for Descriptor
     }
 }
 
+/// The error returned when a descriptor is bound using a name which is not a descriptor variable of
+/// the shaders of the bound pipeline.
+#[derive(Debug)]
+pub struct DescriptorNameError {
+    /// The descriptor names reflected from the pipeline shaders.
+    pub available: Vec<String>,
+
+    /// The requested descriptor name.
+    pub name: String,
+}
+
+impl Display for DescriptorNameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "descriptor `{}` not found (available: {})",
+            self.name,
+            self.available.join(", ")
+        )
+    }
+}
+
+impl Error for DescriptorNameError {}
+
 struct Execution<P>
 where
     P: SharedPointerKind,
@@ -403,6 +433,14 @@ where
         }
     }
 
+    fn descriptor_names(&self) -> &DescriptorNameMap {
+        match self {
+            ExecutionPipeline::Compute(pipeline) => &pipeline.descriptor_names,
+            ExecutionPipeline::Graphic(pipeline) => &pipeline.descriptor_names,
+            ExecutionPipeline::RayTrace(pipeline) => &pipeline.descriptor_names,
+        }
+    }
+
     fn descriptor_info(&self) -> &PipelineDescriptorInfo<P> {
         match self {
             ExecutionPipeline::Compute(pipeline) => &pipeline.descriptor_info,
@@ -446,10 +484,12 @@ struct Pass<P>
 where
     P: SharedPointerKind,
 {
+    correlated_view_mask: u32,
     depth_stencil: Option<DepthStencilMode>,
     execs: Vec<Execution<P>>,
     name: String,
     render_area: Option<Area>,
+    view_mask: u32,
 }
 
 impl<P> Pass<P>
@@ -1112,6 +1152,7 @@ pub enum Subresource {
     AccelerationStructure,
     Image(ImageSubresource),
     Buffer(BufferSubresource),
+    Sampler,
 }
 
 impl Subresource {
@@ -1150,6 +1191,13 @@ impl From<BufferSubresource> for Subresource {
     }
 }
 
+// Samplers have no subresources; this allows them to be bound as descriptors
+impl From<SamplerInfo> for Subresource {
+    fn from(_: SamplerInfo) -> Self {
+        Self::Sampler
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 struct SubresourceAccess {
     access: AccessType,
@@ -2,11 +2,11 @@ use {
     super::{
         AccelerationStructureBinding, AccelerationStructureLeaseBinding, BufferBinding,
         BufferLeaseBinding, ImageBinding, ImageLeaseBinding, Information, NodeIndex, RenderGraph,
-        Subresource,
+        SamplerBinding, Subresource,
     },
     crate::driver::{
-        vk, AccelerationStructureInfo, BufferInfo, BufferSubresource, ImageInfo, ImageSubresource,
-        ImageViewInfo,
+        vk, AccelerationStructureInfo, BufferInfo, BufferSubresource, BufferViewInfo, ImageInfo,
+        ImageSubresource, ImageViewInfo, SamplerInfo,
     },
     archery::{SharedPointer, SharedPointerKind},
     std::{marker::PhantomData, ops::Range},
@@ -204,6 +204,7 @@ node!(Buffer);
 node!(BufferLease);
 node!(Image);
 node!(ImageLease);
+node!(Sampler);
 node!(SwapchainImage);
 
 macro_rules! node_unbind {
@@ -239,6 +240,7 @@ macro_rules! node_unbind {
 node_unbind!(AccelerationStructure);
 node_unbind!(Buffer);
 node_unbind!(Image);
+node_unbind!(Sampler);
 
 macro_rules! node_unbind_lease {
     ($name:ident) => {
@@ -311,7 +313,7 @@ impl<P> View<P> for AnyAccelerationStructureNode<P> {
 }
 
 impl<P> View<P> for AnyBufferNode<P> {
-    type Information = BufferSubresource;
+    type Information = BufferViewInfo;
     type Subresource = BufferSubresource;
 }
 
@@ -321,12 +323,12 @@ impl<P> View<P> for AnyImageNode<P> {
 }
 
 impl<P> View<P> for BufferLeaseNode<P> {
-    type Information = BufferSubresource;
+    type Information = BufferViewInfo;
     type Subresource = BufferSubresource;
 }
 
 impl<P> View<P> for BufferNode<P> {
-    type Information = BufferSubresource;
+    type Information = BufferViewInfo;
     type Subresource = BufferSubresource;
 }
 
@@ -340,6 +342,11 @@ impl<P> View<P> for ImageNode<P> {
     type Subresource = ImageSubresource;
 }
 
+impl<P> View<P> for SamplerNode<P> {
+    type Information = SamplerInfo;
+    type Subresource = SamplerInfo;
+}
+
 impl<P> View<P> for SwapchainImageNode<P> {
     type Information = ImageViewInfo;
     type Subresource = ImageSubresource;
@@ -348,11 +355,12 @@ impl<P> View<P> for SwapchainImageNode<P> {
 pub enum ViewType {
     AccelerationStructure,
     Image(ImageViewInfo),
-    Buffer(Range<vk::DeviceSize>),
+    Buffer(BufferViewInfo),
+    Sampler,
 }
 
 impl ViewType {
-    pub(super) fn as_buffer(&self) -> Option<&Range<vk::DeviceSize>> {
+    pub(super) fn as_buffer(&self) -> Option<&BufferViewInfo> {
         match self {
             Self::Buffer(view_info) => Some(view_info),
             _ => None,
@@ -376,7 +384,13 @@ impl From<()> for ViewType {
 
 impl From<BufferSubresource> for ViewType {
     fn from(subresource: BufferSubresource) -> Self {
-        Self::Buffer(subresource.start..subresource.end)
+        Self::Buffer(subresource.into())
+    }
+}
+
+impl From<BufferViewInfo> for ViewType {
+    fn from(info: BufferViewInfo) -> Self {
+        Self::Buffer(info)
     }
 }
 
@@ -386,8 +400,14 @@ impl From<ImageViewInfo> for ViewType {
     }
 }
 
+impl From<SamplerInfo> for ViewType {
+    fn from(_: SamplerInfo) -> Self {
+        Self::Sampler
+    }
+}
+
 impl From<Range<vk::DeviceSize>> for ViewType {
     fn from(range: Range<vk::DeviceSize>) -> Self {
-        Self::Buffer(range)
+        Self::Buffer(range.into())
     }
 }
@@ -2,21 +2,25 @@ use {
     super::{
         AccelerationStructureLeaseNode, AccelerationStructureNode, AnyAccelerationStructureNode,
         AnyBufferNode, AnyImageNode, Area, AttachmentIndex, Bind, Binding, BufferLeaseNode,
-        BufferNode, Color, Descriptor, Edge, Execution, ExecutionFunction, ExecutionPipeline,
-        ImageLeaseNode, ImageNode, Information, Node, NodeIndex, Pass, RenderGraph, SampleCount,
-        Subresource, SubresourceAccess, SwapchainImageNode, View, ViewType,
+        BufferNode, Color, Descriptor, DescriptorNameError, Edge, Execution, ExecutionFunction,
+        ExecutionPipeline, ImageLeaseNode, ImageNode, Information, Node, NodeIndex, Pass,
+        RenderGraph, SampleCount, SamplerNode, ShaderBindings, ShaderBindingsError, Subresource,
+        SubresourceAccess, SwapchainImageNode, View, ViewType,
     },
     crate::driver::{
         AccelerationStructure, AccelerationStructureGeometryData,
-        AccelerationStructureGeometryInfo, Buffer, ComputePipeline, DepthStencilMode, Device,
-        DeviceOrHostAddress, GraphicPipeline, Image, ImageViewInfo, RayTracePipeline,
+        AccelerationStructureGeometryInfo, BlockLayout, Buffer, ComputePipeline, DepthStencilMode,
+        DescriptorBinding, DescriptorInfo, Device, DeviceOrHostAddress, GraphicPipeline, Image,
+        ImageViewInfo, RayTracePipeline, Sampler,
     },
     archery::{SharedPointer, SharedPointerKind},
-    ash::vk,
-    log::trace,
+    ash::{extensions::ext, vk},
+    bytemuck::{bytes_of, Pod},
+    log::{trace, warn},
     std::{
         cell::RefCell,
         marker::PhantomData,
+        mem::size_of,
         ops::{Index, Range},
     },
     vk_sync::AccessType,
@@ -303,6 +307,7 @@ index!(Buffer, Buffer);
 index!(BufferLease, Buffer);
 index!(Image, Image);
 index!(ImageLease, Image);
+index!(Sampler, Sampler);
 index!(SwapchainImage, Image);
 
 impl<'a, P> Index<AnyAccelerationStructureNode<P>> for Bindings<'a, P>
@@ -433,6 +438,31 @@ where
         self
     }
 
+    /// Updates a single member of the push constant block, found using its name in the shader code
+    /// (such as `light.color`).
+    ///
+    /// _NOTE:_ Panics if the member is not found, or in debug builds if the size of `T` does not
+    /// match the member.
+    pub fn push_constant_named<T>(&self, name: &str, data: &T) -> &Self
+    where
+        T: Pod,
+    {
+        let member = self
+            .pipeline
+            .push_constant_block
+            .iter()
+            .find(|member| member.name == name)
+            .unwrap_or_else(|| panic!("push constant `{name}` not found"));
+
+        debug_assert_eq!(
+            size_of::<T>() as u32,
+            member.size,
+            "push constant `{name}` size mismatch"
+        );
+
+        self.push_constants_offset(member.offset, bytes_of(data))
+    }
+
     pub fn push_constants(&self, data: &[u8]) -> &Self {
         self.push_constants_offset(0, data)
     }
@@ -458,7 +488,7 @@ where
                         self.cmd_buf,
                         self.pipeline.layout,
                         vk::ShaderStageFlags::COMPUTE,
-                        push_const.offset,
+                        start,
                         &data[(start - offset) as usize..(end - offset) as usize],
                     );
                 }
@@ -467,6 +497,23 @@ where
 
         self
     }
+
+    /// Updates push constants using a typed value which starts at offset zero of the push constant
+    /// block.
+    ///
+    /// _NOTE:_ In debug builds, panics if the size or field offsets of `T` do not match the push
+    /// constant block reflected from the shader code.
+    pub fn push_constants_typed<T>(&self, data: &T) -> &Self
+    where
+        T: BlockLayout,
+    {
+        debug_assert!(
+            T::validate_block(&self.pipeline.push_constant_block).is_ok(),
+            "push constants do not match the shader push constant block"
+        );
+
+        self.push_constants(bytes_of(data))
+    }
 }
 
 pub struct Draw<'a, P>
@@ -713,6 +760,154 @@ where
         self
     }
 
+    /// Dispatches task (or mesh, if the pipeline has no task shader) workgroups.
+    ///
+    /// _NOTE:_ Requires `DriverConfig::mesh_shading`; otherwise this does nothing.
+    pub fn draw_mesh_tasks(
+        &self,
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    ) -> &Self {
+        if let Some(mesh_shader_ext) = self.mesh_shader_ext() {
+            unsafe {
+                mesh_shader_ext.cmd_draw_mesh_tasks(
+                    self.cmd_buf,
+                    group_count_x,
+                    group_count_y,
+                    group_count_z,
+                );
+            }
+        }
+
+        self
+    }
+
+    /// Dispatches task (or mesh) workgroups using `vk::DrawMeshTasksIndirectCommandEXT` values
+    /// stored in a buffer.
+    ///
+    /// _NOTE:_ Requires `DriverConfig::mesh_shading`; otherwise this does nothing.
+    pub fn draw_mesh_tasks_indirect(
+        &self,
+        buffer: impl Into<AnyBufferNode<P>>,
+        offset: vk::DeviceSize,
+        draw_count: u32,
+        stride: u32,
+    ) -> &Self {
+        let buffer = buffer.into();
+
+        if let Some(mesh_shader_ext) = self.mesh_shader_ext() {
+            unsafe {
+                mesh_shader_ext.cmd_draw_mesh_tasks_indirect(
+                    self.cmd_buf,
+                    *self.bindings[buffer],
+                    offset,
+                    draw_count,
+                    stride,
+                );
+            }
+        }
+
+        self
+    }
+
+    /// Dispatches task (or mesh) workgroups using `vk::DrawMeshTasksIndirectCommandEXT` values
+    /// stored in a buffer, with the draw count read from another buffer.
+    ///
+    /// _NOTE:_ Requires `DriverConfig::mesh_shading`; otherwise this does nothing.
+    pub fn draw_mesh_tasks_indirect_count(
+        &self,
+        buffer: impl Into<AnyBufferNode<P>>,
+        offset: vk::DeviceSize,
+        count_buf: impl Into<AnyBufferNode<P>>,
+        count_buf_offset: vk::DeviceSize,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> &Self {
+        let buffer = buffer.into();
+        let count_buf = count_buf.into();
+
+        if let Some(mesh_shader_ext) = self.mesh_shader_ext() {
+            unsafe {
+                mesh_shader_ext.cmd_draw_mesh_tasks_indirect_count(
+                    self.cmd_buf,
+                    *self.bindings[buffer],
+                    offset,
+                    *self.bindings[count_buf],
+                    count_buf_offset,
+                    max_draw_count,
+                    stride,
+                );
+            }
+        }
+
+        self
+    }
+
+    /// Returns the extended dynamic state extension if the bound pipeline uses it, otherwise warns
+    /// that the given state is not dynamic.
+    fn extended_dynamic_state_ext(&self, state: &str) -> Option<&ext::ExtendedDynamicState> {
+        if !self.is_dynamic_state(state) {
+            return None;
+        }
+
+        let extended_dynamic_state_ext = self.device.extended_dynamic_state_ext.as_ref();
+
+        if extended_dynamic_state_ext.is_none() {
+            warn!("{state} is not dynamic: VK_EXT_extended_dynamic_state is not supported");
+        }
+
+        extended_dynamic_state_ext
+    }
+
+    /// Returns `true` if the bound pipeline uses dynamic state, otherwise warns that the given
+    /// state is not dynamic.
+    fn is_dynamic_state(&self, state: &str) -> bool {
+        let is_dynamic_state = self.pipeline.info.dynamic_state;
+
+        if !is_dynamic_state {
+            warn!("{state} is not dynamic: GraphicPipelineInfo::dynamic_state is not enabled");
+        }
+
+        is_dynamic_state
+    }
+
+    /// Returns the mesh shader extension, otherwise warns that mesh tasks cannot be drawn.
+    fn mesh_shader_ext(&self) -> Option<&ext::MeshShader> {
+        let mesh_shader_ext = self.device.mesh_shader_ext.as_ref();
+
+        if mesh_shader_ext.is_none() {
+            warn!("unable to draw mesh tasks: DriverConfig::mesh_shading is not enabled");
+        }
+
+        mesh_shader_ext
+    }
+
+    /// Updates a single member of the push constant block, found using its name in the shader code
+    /// (such as `light.color`).
+    ///
+    /// _NOTE:_ Panics if the member is not found, or in debug builds if the size of `T` does not
+    /// match the member.
+    pub fn push_constant_named<T>(&self, name: &str, data: &T) -> &Self
+    where
+        T: Pod,
+    {
+        let member = self
+            .pipeline
+            .push_constant_block
+            .iter()
+            .find(|member| member.name == name)
+            .unwrap_or_else(|| panic!("push constant `{name}` not found"));
+
+        debug_assert_eq!(
+            size_of::<T>() as u32,
+            member.size,
+            "push constant `{name}` size mismatch"
+        );
+
+        self.push_constants_offset(member.offset, bytes_of(data))
+    }
+
     pub fn push_constants(&self, data: &[u8]) -> &Self {
         self.push_constants_offset(0, data)
     }
@@ -748,6 +943,160 @@ where
         self
     }
 
+    /// Updates push constants using a typed value which starts at offset zero of the push constant
+    /// block.
+    ///
+    /// _NOTE:_ In debug builds, panics if the size or field offsets of `T` do not match the push
+    /// constant block reflected from the shader code.
+    pub fn push_constants_typed<T>(&self, data: &T) -> &Self
+    where
+        T: BlockLayout,
+    {
+        debug_assert!(
+            T::validate_block(&self.pipeline.push_constant_block).is_ok(),
+            "push constants do not match the shader push constant block"
+        );
+
+        self.push_constants(bytes_of(data))
+    }
+
+    /// Sets the constant color used by the `CONSTANT_*` blend factors.
+    ///
+    /// _NOTE:_ Requires `GraphicPipelineInfo::blend_constants` to be `None`; otherwise this does
+    /// nothing.
+    pub fn set_blend_constants(&self, blend_constants: [f32; 4]) -> &Self {
+        if self.pipeline.info.blend_constants.is_none() {
+            unsafe {
+                self.device
+                    .cmd_set_blend_constants(self.cmd_buf, &blend_constants);
+            }
+        } else {
+            warn!("blend constants are not dynamic: GraphicPipelineInfo::blend_constants is set");
+        }
+
+        self
+    }
+
+    /// Sets the cull mode, overriding `GraphicPipelineInfo::cull_mode`.
+    ///
+    /// _NOTE:_ Requires `GraphicPipelineInfo::dynamic_state` and `VK_EXT_extended_dynamic_state`
+    /// (see `Device::extended_dynamic_state_ext`); otherwise this does nothing.
+    pub fn set_cull_mode(&self, cull_mode: vk::CullModeFlags) -> &Self {
+        if let Some(extended_dynamic_state_ext) = self.extended_dynamic_state_ext("cull mode") {
+            unsafe {
+                extended_dynamic_state_ext.cmd_set_cull_mode(self.cmd_buf, cull_mode);
+            }
+        }
+
+        self
+    }
+
+    /// Sets the depth bias used when rasterizing polygons, such as when rendering shadow maps.
+    ///
+    /// _NOTE:_ Requires `GraphicPipelineInfo::dynamic_state`; otherwise this does nothing. A
+    /// non-zero clamp requires the `depthBiasClamp` device feature.
+    pub fn set_depth_bias(&self, constant_factor: f32, clamp: f32, slope_factor: f32) -> &Self {
+        if self.is_dynamic_state("depth bias") {
+            unsafe {
+                self.device
+                    .cmd_set_depth_bias(self.cmd_buf, constant_factor, clamp, slope_factor);
+            }
+        }
+
+        self
+    }
+
+    /// Sets the depth bounds used when `DepthStencilMode::bounds_test` is enabled.
+    ///
+    /// _NOTE:_ Requires `GraphicPipelineInfo::dynamic_state`; otherwise this does nothing.
+    pub fn set_depth_bounds(&self, depth_bounds: Range<f32>) -> &Self {
+        if self.is_dynamic_state("depth bounds") {
+            unsafe {
+                self.device.cmd_set_depth_bounds(
+                    self.cmd_buf,
+                    depth_bounds.start,
+                    depth_bounds.end,
+                );
+            }
+        }
+
+        self
+    }
+
+    /// Sets the depth comparison operator, overriding `DepthStencilMode::compare_op`.
+    ///
+    /// _NOTE:_ Requires `GraphicPipelineInfo::dynamic_state` and `VK_EXT_extended_dynamic_state`
+    /// (see `Device::extended_dynamic_state_ext`); otherwise this does nothing.
+    pub fn set_depth_compare_op(&self, compare_op: vk::CompareOp) -> &Self {
+        if let Some(extended_dynamic_state_ext) =
+            self.extended_dynamic_state_ext("depth compare op")
+        {
+            unsafe {
+                extended_dynamic_state_ext.cmd_set_depth_compare_op(self.cmd_buf, compare_op);
+            }
+        }
+
+        self
+    }
+
+    /// Enables or disables depth testing, overriding `DepthStencilMode::depth_test`.
+    ///
+    /// _NOTE:_ Requires `GraphicPipelineInfo::dynamic_state` and `VK_EXT_extended_dynamic_state`
+    /// (see `Device::extended_dynamic_state_ext`); otherwise this does nothing.
+    pub fn set_depth_test(&self, depth_test: bool) -> &Self {
+        if let Some(extended_dynamic_state_ext) = self.extended_dynamic_state_ext("depth test") {
+            unsafe {
+                extended_dynamic_state_ext.cmd_set_depth_test_enable(self.cmd_buf, depth_test);
+            }
+        }
+
+        self
+    }
+
+    /// Enables or disables depth writes, overriding `DepthStencilMode::depth_write`.
+    ///
+    /// _NOTE:_ Requires `GraphicPipelineInfo::dynamic_state` and `VK_EXT_extended_dynamic_state`
+    /// (see `Device::extended_dynamic_state_ext`); otherwise this does nothing.
+    pub fn set_depth_write(&self, depth_write: bool) -> &Self {
+        if let Some(extended_dynamic_state_ext) = self.extended_dynamic_state_ext("depth write") {
+            unsafe {
+                extended_dynamic_state_ext.cmd_set_depth_write_enable(self.cmd_buf, depth_write);
+            }
+        }
+
+        self
+    }
+
+    /// Sets the front face, overriding `GraphicPipelineInfo::front_face`.
+    ///
+    /// _NOTE:_ Requires `GraphicPipelineInfo::dynamic_state` and `VK_EXT_extended_dynamic_state`
+    /// (see `Device::extended_dynamic_state_ext`); otherwise this does nothing.
+    pub fn set_front_face(&self, front_face: vk::FrontFace) -> &Self {
+        if let Some(extended_dynamic_state_ext) = self.extended_dynamic_state_ext("front face") {
+            unsafe {
+                extended_dynamic_state_ext.cmd_set_front_face(self.cmd_buf, front_face);
+            }
+        }
+
+        self
+    }
+
+    /// Sets the width of rasterized lines.
+    ///
+    /// _NOTE:_ Requires `GraphicPipelineInfo::line_width` to be `None`; otherwise this does
+    /// nothing.
+    pub fn set_line_width(&self, line_width: f32) -> &Self {
+        if self.pipeline.info.line_width.is_none() {
+            unsafe {
+                self.device.cmd_set_line_width(self.cmd_buf, line_width);
+            }
+        } else {
+            warn!("line width is not dynamic: GraphicPipelineInfo::line_width is set");
+        }
+
+        self
+    }
+
     pub fn set_scissor(&self, x: i32, y: i32, width: u32, height: u32) -> &Self {
         unsafe {
             self.device.cmd_set_scissor(
@@ -786,6 +1135,56 @@ where
         self
     }
 
+    /// Sets the bits of stencil values which are compared during the stencil test.
+    ///
+    /// _NOTE:_ Requires `GraphicPipelineInfo::dynamic_state`; otherwise this does nothing.
+    pub fn set_stencil_compare_mask(
+        &self,
+        face_mask: vk::StencilFaceFlags,
+        compare_mask: u32,
+    ) -> &Self {
+        if self.is_dynamic_state("stencil compare mask") {
+            unsafe {
+                self.device
+                    .cmd_set_stencil_compare_mask(self.cmd_buf, face_mask, compare_mask);
+            }
+        }
+
+        self
+    }
+
+    /// Sets the reference value used during the stencil test.
+    ///
+    /// _NOTE:_ Requires `GraphicPipelineInfo::dynamic_state`; otherwise this does nothing.
+    pub fn set_stencil_reference(&self, face_mask: vk::StencilFaceFlags, reference: u32) -> &Self {
+        if self.is_dynamic_state("stencil reference") {
+            unsafe {
+                self.device
+                    .cmd_set_stencil_reference(self.cmd_buf, face_mask, reference);
+            }
+        }
+
+        self
+    }
+
+    /// Sets the bits of stencil values which are written by the stencil test.
+    ///
+    /// _NOTE:_ Requires `GraphicPipelineInfo::dynamic_state`; otherwise this does nothing.
+    pub fn set_stencil_write_mask(
+        &self,
+        face_mask: vk::StencilFaceFlags,
+        write_mask: u32,
+    ) -> &Self {
+        if self.is_dynamic_state("stencil write mask") {
+            unsafe {
+                self.device
+                    .cmd_set_stencil_write_mask(self.cmd_buf, face_mask, write_mask);
+            }
+        }
+
+        self
+    }
+
     pub fn set_viewport(
         &self,
         x: f32,
@@ -852,10 +1251,12 @@ where
     pub(super) fn new(graph: &'a mut RenderGraph<P>, name: String) -> PassRef<'a, P> {
         let pass_idx = graph.passes.len();
         graph.passes.push(Pass {
+            correlated_view_mask: 0,
             depth_stencil: None,
             execs: vec![Default::default()], // We start off with a default execution!
             name,
             render_area: None,
+            view_mask: 0,
         });
 
         Self {
@@ -1116,6 +1517,90 @@ where
         self
     }
 
+    /// Binds a node to a descriptor using the access type inferred from the bound pipeline.
+    ///
+    /// The access type depends on the descriptor type, the shader stages which use it, and the
+    /// `readonly`/`writeonly` qualifiers of storage descriptors. Input attachments use the default
+    /// read access of the pipeline.
+    ///
+    /// _NOTE:_ Panics if the descriptor is not used by the bound pipeline.
+    pub fn bind_descriptor<N>(self, descriptor: impl Into<Descriptor>, node: N) -> Self
+    where
+        N: Information,
+        N: View<P>,
+        ViewType: From<<N as View<P>>::Information>,
+        <N as View<P>>::Information: From<<N as Information>::Info>,
+        <N as View<P>>::Subresource: From<<N as View<P>>::Information>,
+    {
+        let descriptor = descriptor.into();
+        let (descriptor_set_idx, binding_idx, _) = descriptor.into_tuple();
+        let (descriptor_info, stages) = self
+            .pass
+            .as_ref()
+            .execs
+            .last()
+            .unwrap()
+            .pipeline
+            .as_ref()
+            .unwrap()
+            .descriptor_bindings()
+            .get(&DescriptorBinding(descriptor_set_idx, binding_idx))
+            .copied()
+            .unwrap_or_else(|| panic!("descriptor {descriptor:?} not found in pipeline"));
+        let access = descriptor_info
+            .access_type(stages)
+            .unwrap_or(<T as Access>::DEFAULT_READ);
+
+        self.access_descriptor(descriptor, node, access)
+    }
+
+    /// Binds each node of a [`ShaderBindings`] group to its descriptor.
+    ///
+    /// Returns an error naming the first field which does not match the descriptors of the bound
+    /// pipeline; see [`ShaderDescriptors::validate`](super::ShaderDescriptors::validate).
+    pub fn bind_shader_bindings<B>(self, bindings: B) -> Result<Self, ShaderBindingsError>
+    where
+        B: ShaderBindings<P>,
+    {
+        let descriptor_bindings = self
+            .pass
+            .as_ref()
+            .execs
+            .last()
+            .unwrap()
+            .pipeline
+            .as_ref()
+            .unwrap()
+            .descriptor_bindings();
+
+        B::validate(descriptor_bindings)?;
+
+        Ok(bindings.bind(self))
+    }
+
+    fn named_descriptor(&self, name: &str) -> Result<Descriptor, DescriptorNameError> {
+        let descriptor_names = self
+            .pass
+            .as_ref()
+            .execs
+            .last()
+            .unwrap()
+            .pipeline
+            .as_ref()
+            .unwrap()
+            .descriptor_names();
+
+        descriptor_names
+            .get(name)
+            .map(|&DescriptorBinding(descriptor_set_idx, binding_idx)| {
+                Descriptor::Binding(descriptor_set_idx, binding_idx)
+            })
+            .ok_or_else(|| DescriptorNameError {
+                available: descriptor_names.keys().cloned().collect(),
+                name: name.to_owned(),
+            })
+    }
+
     fn push_node_view_bind(
         &mut self,
         node: impl Node<P>,
@@ -1125,6 +1610,35 @@ where
         let node_idx = node.index();
         self.pass.assert_bound_graph_node(node);
 
+        let view_info = view_info.into();
+
+        // Texel buffers need a format, which the default view of a buffer does not have
+        if let Some(buffer_view_info) = view_info.as_buffer() {
+            let (descriptor_set_idx, binding_idx, _) = binding.into_tuple();
+            let is_texel_buffer = matches!(
+                self.pass
+                    .as_ref()
+                    .execs
+                    .last()
+                    .unwrap()
+                    .pipeline
+                    .as_ref()
+                    .unwrap()
+                    .descriptor_bindings()
+                    .get(&DescriptorBinding(descriptor_set_idx, binding_idx)),
+                Some((
+                    DescriptorInfo::StorageTexelBuffer(..) | DescriptorInfo::UniformTexelBuffer(_),
+                    _
+                ))
+            );
+
+            assert!(
+                !is_texel_buffer || buffer_view_info.fmt != vk::Format::UNDEFINED,
+                "descriptor {binding:?} is a texel buffer which requires a format: use \
+                `read_descriptor_as` or `write_descriptor_as` with a `BufferViewInfo`"
+            );
+        }
+
         assert!(
             self.pass
                 .as_mut()
@@ -1132,7 +1646,7 @@ where
                 .last_mut()
                 .unwrap()
                 .bindings
-                .insert(binding, (node_idx, Some(view_info.into())))
+                .insert(binding, (node_idx, Some(view_info)))
                 .is_none(),
             "descriptor {binding:?} has already been bound"
         );
@@ -1168,6 +1682,24 @@ where
         self.read_descriptor_subrange(descriptor, node, view_info, subresource)
     }
 
+    /// Like [`Self::read_descriptor`], but finds the descriptor using the name of its variable in
+    /// the shader code, instead of a set and binding index.
+    ///
+    /// _NOTE:_ Names which refer to different descriptors in different stages of the pipeline are
+    /// ambiguous and are not found.
+    pub fn read_descriptor_named<N>(self, name: &str, node: N) -> Result<Self, DescriptorNameError>
+    where
+        N: Information,
+        N: View<P>,
+        ViewType: From<<N as View<P>>::Information>,
+        <N as View<P>>::Information: From<<N as Information>::Info>,
+        <N as View<P>>::Subresource: From<<N as View<P>>::Information>,
+    {
+        let descriptor = self.named_descriptor(name)?;
+
+        Ok(self.read_descriptor(descriptor, node))
+    }
+
     pub fn read_descriptor_subrange<N>(
         self,
         descriptor: impl Into<Descriptor>,
@@ -1230,6 +1762,23 @@ where
         self.write_descriptor_subrange(descriptor, node, view_info, subresource)
     }
 
+    /// Like [`Self::write_descriptor`], but finds the descriptor using the name of its variable in
+    /// the shader code, instead of a set and binding index.
+    ///
+    /// _NOTE:_ See [`Self::read_descriptor_named`] for ambiguous names.
+    pub fn write_descriptor_named<N>(self, name: &str, node: N) -> Result<Self, DescriptorNameError>
+    where
+        N: Information,
+        N: View<P>,
+        <N as View<P>>::Information: Into<ViewType>,
+        <N as View<P>>::Information: From<<N as Information>::Info>,
+        <N as View<P>>::Subresource: From<<N as View<P>>::Information>,
+    {
+        let descriptor = self.named_descriptor(name)?;
+
+        Ok(self.write_descriptor(descriptor, node))
+    }
+
     pub fn write_descriptor_subrange<N>(
         self,
         descriptor: impl Into<Descriptor>,
@@ -1921,6 +2470,23 @@ where
         self
     }
 
+    /// Enables multiview rendering: each bit of `view_mask` is an array layer of every attachment
+    /// which is rendered by each draw of this pass, such as the six faces of a cubemap or the two
+    /// eyes of a stereo pair. Shaders may read the current view using `gl_ViewIndex`.
+    ///
+    /// `correlated_view_mask` is a hint that the given views are spatially similar and may be
+    /// rendered concurrently; use zero if there is no correlation.
+    ///
+    /// _NOTE:_ Attachments must have at least as many array layers as the highest view of the mask
+    /// and the device must support multiview (see `Device::multiview_properties`).
+    pub fn set_multiview(&mut self, view_mask: u32, correlated_view_mask: u32) -> &mut Self {
+        let pass = self.pass.as_mut();
+        pass.correlated_view_mask = correlated_view_mask;
+        pass.view_mask = view_mask;
+
+        self
+    }
+
     /// Sets the `[renderArea](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/VkRenderPassBeginInfo.html#_c_specification)`
     /// field when beginning a render pass.
     ///
@@ -2150,8 +2716,24 @@ where
     P: SharedPointerKind + Send + 'static,
 {
     pub fn record_ray_trace(mut self, func: impl FnOnce(RayTrace<'_, P>) + Send + 'static) -> Self {
+        let pipeline = SharedPointer::clone(
+            self.pass
+                .as_ref()
+                .execs
+                .last()
+                .unwrap()
+                .pipeline
+                .as_ref()
+                .unwrap()
+                .unwrap_ray_trace(),
+        );
+
         self.pass.push_execute(move |device, cmd_buf, _bindings| {
-            func(RayTrace { cmd_buf, device });
+            func(RayTrace {
+                cmd_buf,
+                device,
+                pipeline,
+            });
         });
 
         self
@@ -2164,12 +2746,90 @@ where
 {
     cmd_buf: vk::CommandBuffer,
     device: &'a Device<P>,
+    pipeline: SharedPointer<RayTracePipeline<P>, P>,
 }
 
 impl<'a, P> RayTrace<'a, P>
 where
     P: SharedPointerKind,
 {
+    /// Updates a single member of the push constant block, found using its name in the shader code
+    /// (such as `light.color`).
+    ///
+    /// _NOTE:_ Panics if the member is not found, or in debug builds if the size of `T` does not
+    /// match the member.
+    pub fn push_constant_named<T>(&self, name: &str, data: &T) -> &Self
+    where
+        T: Pod,
+    {
+        let member = self
+            .pipeline
+            .push_constant_block
+            .iter()
+            .find(|member| member.name == name)
+            .unwrap_or_else(|| panic!("push constant `{name}` not found"));
+
+        debug_assert_eq!(
+            size_of::<T>() as u32,
+            member.size,
+            "push constant `{name}` size mismatch"
+        );
+
+        self.push_constants_offset(member.offset, bytes_of(data))
+    }
+
+    pub fn push_constants(&self, data: &[u8]) -> &Self {
+        self.push_constants_offset(0, data)
+    }
+
+    pub fn push_constants_offset(&self, offset: u32, data: &[u8]) -> &Self {
+        if let Some(push_const) = &self.pipeline.push_constants {
+            // Determine the range of the overall pipline push constants which overlap with `data`
+            let push_const_end = push_const.offset + push_const.size;
+            let data_end = offset + data.len() as u32;
+            let end = data_end.min(push_const_end);
+            let start = offset.max(push_const.offset);
+
+            if end > start {
+                trace!(
+                    "      push constants {:?} {}..{}",
+                    push_const.stage_flags,
+                    start,
+                    end
+                );
+
+                unsafe {
+                    self.device.cmd_push_constants(
+                        self.cmd_buf,
+                        self.pipeline.layout,
+                        push_const.stage_flags,
+                        start,
+                        &data[(start - offset) as usize..(end - offset) as usize],
+                    );
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Updates push constants using a typed value which starts at offset zero of the push constant
+    /// block.
+    ///
+    /// _NOTE:_ In debug builds, panics if the size or field offsets of `T` do not match the push
+    /// constant block reflected from the shader code.
+    pub fn push_constants_typed<T>(&self, data: &T) -> &Self
+    where
+        T: BlockLayout,
+    {
+        debug_assert!(
+            T::validate_block(&self.pipeline.push_constant_block).is_ok(),
+            "push constants do not match the shader push constant block"
+        );
+
+        self.push_constants(bytes_of(data))
+    }
+
     // TODO: If the rayTraversalPrimitiveCulling or rayQuery features are enabled, the SkipTrianglesKHR and SkipAABBsKHR ray flags can be specified when tracing a ray. SkipTrianglesKHR and SkipAABBsKHR are mutually exclusive.
 
     #[allow(clippy::too_many_arguments)]
@@ -6,17 +6,17 @@ use {
     crate::{
         driver::{
             format_aspect_mask, image_access_layout, is_read_access, is_write_access,
-            pipeline_stage_access_flags, AttachmentInfo, AttachmentRef, CommandBuffer,
+            pipeline_stage_access_flags, AttachmentInfo, AttachmentRef, Buffer, CommandBuffer,
             DepthStencilMode, DescriptorBinding, DescriptorInfo, DescriptorPool,
             DescriptorPoolInfo, DescriptorPoolSize, DescriptorSet, Device, DriverError,
-            FramebufferKey, FramebufferKeyAttachment, Image, ImageViewInfo, RenderPass,
+            FramebufferKey, FramebufferKeyAttachment, Image, ImageType, ImageViewInfo, RenderPass,
             RenderPassInfo, SampleCount, SubpassDependency, SubpassInfo,
         },
         HashPool, Lease,
     },
     archery::{SharedPointer, SharedPointerKind},
     ash::vk,
-    log::{debug, trace},
+    log::{debug, trace, warn},
     std::{
         cell::RefCell,
         collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
@@ -104,6 +104,13 @@ where
             return false;
         }
 
+        // Must be same multiview modes
+        if lhs.view_mask != rhs.view_mask || lhs.correlated_view_mask != rhs.correlated_view_mask {
+            trace!("  different multiview modes");
+
+            return false;
+        }
+
         let rhs_first_exec = rhs.execs.first().unwrap();
 
         // Now we need to know what the subpasses (we may have prior merges) wrote
@@ -211,6 +218,10 @@ where
 
         let physical_pass = &self.physical_passes[pass_idx];
         let render_pass = physical_pass.render_pass.as_ref().unwrap();
+
+        // Multiview attachments are viewed as arrays with one layer per view
+        let view_count = u32::BITS - pass.view_mask.leading_zeros();
+
         let attached_images = {
             let mut attachment_queue =
                 (0..render_pass.info.attachments.len()).collect::<VecDeque<_>>();
@@ -222,14 +233,24 @@ where
                         let image = self.graph.bindings[attachment.target]
                             .as_driver_image()
                             .unwrap();
+                        let (array_layer_count, ty) = if view_count == 0 {
+                            (1, image.info.ty)
+                        } else {
+                            debug_assert!(
+                                image.info.array_elements >= view_count,
+                                "multiview attachment {attachment_idx} requires {view_count} array layers"
+                            );
+
+                            (view_count, ImageType::TextureArray2D)
+                        };
                         let view_info = ImageViewInfo {
-                            array_layer_count: Some(1),
+                            array_layer_count: Some(array_layer_count),
                             aspect_mask: attachment.aspect_mask,
                             base_array_layer: 0,
                             base_mip_level: 0,
                             fmt: attachment.fmt,
                             mip_level_count: Some(1),
-                            ty: image.info.ty,
+                            ty,
                         };
 
                         trace!("attachment {attachment_idx}: {image:?}");
@@ -254,7 +275,11 @@ where
                     usage: image.info.usage,
                     extent_x: image.info.width,
                     extent_y: image.info.height,
-                    layer_count: image.info.array_elements,
+                    layer_count: if view_count == 0 {
+                        image.info.array_elements
+                    } else {
+                        view_count
+                    },
                     view_fmts: pass
                         .execs
                         .iter()
@@ -351,6 +376,8 @@ where
         }
     }
 
+    /// Binds the pipeline of an execution, returning `false` if it is a graphic pipeline which is
+    /// still being created in the background.
     fn bind_pipeline(
         &self,
         cmd_buf: &mut CommandBuffer<P>,
@@ -358,7 +385,7 @@ where
         exec_idx: usize,
         pipeline: &mut ExecutionPipeline<P>,
         depth_stencil: Option<DepthStencilMode>,
-    ) -> Result<(), DriverError> {
+    ) -> Result<bool, DriverError> {
         let (ty, name, vk_pipeline) = match pipeline {
             ExecutionPipeline::Compute(pipeline) => {
                 ("compute", pipeline.info.name.as_ref(), ***pipeline)
@@ -376,6 +403,13 @@ where
             trace!("    bind {} pipeline {:?}", ty, vk_pipeline);
         }
 
+        let rasterization = match pipeline {
+            ExecutionPipeline::Graphic(pipeline) if pipeline.info.dynamic_state => {
+                Some((pipeline.info.cull_mode, pipeline.info.front_face))
+            }
+            _ => None,
+        };
+
         // We store a shared reference to this pipeline inside the command buffer!
         let physical_pass = &self.physical_passes[pass_idx];
         let pipeline_bind_point = pipeline.bind_point();
@@ -386,11 +420,24 @@ where
             }
             ExecutionPipeline::Graphic(pipeline) => {
                 CommandBuffer::push_fenced_drop(cmd_buf, SharedPointer::clone(pipeline));
-                physical_pass
-                    .render_pass
-                    .as_ref()
-                    .unwrap()
-                    .graphic_pipeline_ref(pipeline, depth_stencil, exec_idx as _)?
+                let render_pass = physical_pass.render_pass.as_ref().unwrap();
+
+                if pipeline.info.background_compile {
+                    match render_pass.try_graphic_pipeline_ref(
+                        pipeline,
+                        depth_stencil,
+                        exec_idx as _,
+                    )? {
+                        Some(pipeline) => pipeline,
+                        None => {
+                            trace!("    pipeline not ready");
+
+                            return Ok(false);
+                        }
+                    }
+                } else {
+                    render_pass.graphic_pipeline_ref(pipeline, depth_stencil, exec_idx as _)?
+                }
             }
             ExecutionPipeline::RayTrace(pipeline) => {
                 CommandBuffer::push_fenced_drop(cmd_buf, SharedPointer::clone(pipeline));
@@ -404,7 +451,11 @@ where
                 .cmd_bind_pipeline(**cmd_buf, pipeline_bind_point, pipeline);
         }
 
-        Ok(())
+        if let Some((cull_mode, front_face)) = rasterization {
+            Self::set_dynamic_state(cmd_buf, cull_mode, front_face, depth_stencil);
+        }
+
+        Ok(true)
     }
 
     /// Finds the unique indexes of the passes which write to a given node; with the restriction
@@ -539,6 +590,26 @@ where
         // - RenderPass didn't require an Info instance: who cares it's OURS for like five seconds
         //   and then poof
         let pass = &self.graph.passes[pass_idx];
+
+        if pass.view_mask != 0 {
+            let view_count = u32::BITS - pass.view_mask.leading_zeros();
+            let max_view_count = cache
+                .device
+                .multiview_properties
+                .as_ref()
+                .map(|props| props.max_multiview_view_count)
+                .unwrap_or_default();
+
+            if view_count > max_view_count {
+                warn!(
+                    "unsupported multiview view mask {:#b} (max view count {max_view_count})",
+                    pass.view_mask
+                );
+
+                return Err(DriverError::Unsupported);
+            }
+        }
+
         let attachment_count = pass
             .execs
             .iter()
@@ -799,6 +870,7 @@ where
                 .map(|pipeline| pipeline.unwrap_graphic())
                 .unwrap();
             let mut subpass_info = SubpassInfo::with_capacity(attachment_count);
+            subpass_info.view_mask = pass.view_mask;
 
             // TODO: TLS a sorted vec so we don't need to iter.find the input attachments later!
             // Add input attachments
@@ -991,7 +1063,7 @@ where
                                 }
 
                                 // Does the execution have more than one view?
-                                if subpasses[exec_idx].has_multiple_attachments() {
+                                if subpasses[exec_idx].view_mask != 0 {
                                     dep.dependency_flags |= vk::DependencyFlags::VIEW_LOCAL;
                                 }
 
@@ -1051,11 +1123,6 @@ where
                                     dep.dependency_flags |= vk::DependencyFlags::BY_REGION;
                                 }
 
-                                // If the subpass has more than one view we need the VIEW_LOCAL flag
-                                if subpasses[exec_idx].has_multiple_attachments() {
-                                    dep.dependency_flags |= vk::DependencyFlags::VIEW_LOCAL;
-                                }
-
                                 curr_stages &= !common_stages;
                                 curr_access &= !prev_access;
 
@@ -1225,9 +1292,16 @@ where
                 dependencies.into_values().collect::<Vec<_>>()
             };
 
+        let correlated_view_masks = if pass.correlated_view_mask != 0 {
+            vec![pass.correlated_view_mask]
+        } else {
+            vec![]
+        };
+
         cache.lease(
             RenderPassInfo::new()
                 .attachments(attachments)
+                .correlated_view_masks(correlated_view_masks)
                 .dependencies(dependencies)
                 .subpasses(subpasses),
         )
@@ -1765,9 +1839,28 @@ where
                     Self::next_subpass(cmd_buf);
                 }
 
+                let mut is_skipped = false;
+
                 if let Some(pipeline) = &mut pass.execs[exec_idx].pipeline.as_mut() {
-                    self.bind_pipeline(cmd_buf, pass_idx, exec_idx, pipeline, pass.depth_stencil)?;
+                    is_skipped = !self.bind_pipeline(
+                        cmd_buf,
+                        pass_idx,
+                        exec_idx,
+                        pipeline,
+                        pass.depth_stencil,
+                    )?;
+                }
 
+                if is_skipped {
+                    // Nothing is recorded until the background pipeline is ready
+                    trace!("    > exec[{exec_idx}] skipped");
+
+                    pass.execs[exec_idx].func.take();
+
+                    continue;
+                }
+
+                if let Some(pipeline) = &mut pass.execs[exec_idx].pipeline.as_mut() {
                     if is_graphic && pass.render_area.is_none() {
                         let render_area = render_area.unwrap();
                         // In this case we set the viewport and scissor for the user
@@ -2047,6 +2140,53 @@ where
         schedule
     }
 
+    /// Sets the dynamic state of graphic pipelines which use `GraphicPipelineInfo::dynamic_state`
+    /// to the values of the static pipeline state.
+    fn set_dynamic_state(
+        cmd_buf: &CommandBuffer<P>,
+        cull_mode: vk::CullModeFlags,
+        front_face: vk::FrontFace,
+        depth_stencil: Option<DepthStencilMode>,
+    ) {
+        let depth_stencil = depth_stencil
+            .map(DepthStencilMode::into_vk)
+            .unwrap_or_default();
+
+        unsafe {
+            let device = &cmd_buf.device;
+            device.cmd_set_depth_bias(**cmd_buf, 0.0, 0.0, 0.0);
+            device.cmd_set_depth_bounds(
+                **cmd_buf,
+                depth_stencil.min_depth_bounds,
+                depth_stencil.max_depth_bounds,
+            );
+
+            for (face_mask, stencil) in [
+                (vk::StencilFaceFlags::FRONT, depth_stencil.front),
+                (vk::StencilFaceFlags::BACK, depth_stencil.back),
+            ] {
+                device.cmd_set_stencil_compare_mask(**cmd_buf, face_mask, stencil.compare_mask);
+                device.cmd_set_stencil_reference(**cmd_buf, face_mask, stencil.reference);
+                device.cmd_set_stencil_write_mask(**cmd_buf, face_mask, stencil.write_mask);
+            }
+
+            if let Some(extended_dynamic_state_ext) = &device.extended_dynamic_state_ext {
+                extended_dynamic_state_ext.cmd_set_cull_mode(**cmd_buf, cull_mode);
+                extended_dynamic_state_ext
+                    .cmd_set_depth_compare_op(**cmd_buf, depth_stencil.depth_compare_op);
+                extended_dynamic_state_ext.cmd_set_depth_test_enable(
+                    **cmd_buf,
+                    depth_stencil.depth_test_enable == vk::TRUE,
+                );
+                extended_dynamic_state_ext.cmd_set_depth_write_enable(
+                    **cmd_buf,
+                    depth_stencil.depth_write_enable == vk::TRUE,
+                );
+                extended_dynamic_state_ext.cmd_set_front_face(**cmd_buf, front_face);
+            }
+        }
+    }
+
     fn set_scissor(cmd_buf: &CommandBuffer<P>, width: u32, height: u32) {
         use std::slice::from_ref;
 
@@ -2172,6 +2312,8 @@ where
             descriptors: Vec<vk::WriteDescriptorSet>,
             image_infos: Vec<vk::DescriptorImageInfo>,
             image_writes: Vec<IndexWrite>,
+            texel_buffer_views: Vec<vk::BufferView>,
+            texel_buffer_writes: Vec<IndexWrite>,
         }
 
         WRITES.with(|writes| {
@@ -2184,6 +2326,8 @@ where
                 descriptors,
                 image_infos,
                 image_writes,
+                texel_buffer_views,
+                texel_buffer_writes,
             } = &mut *writes.borrow_mut();
             accel_struct_infos.clear();
             accel_struct_writes.clear();
@@ -2192,6 +2336,8 @@ where
             descriptors.clear();
             image_infos.clear();
             image_writes.clear();
+            texel_buffer_views.clear();
+            texel_buffer_writes.clear();
 
             let descriptor_sets = &self.physical_passes[pass_idx].exec_descriptor_sets;
             for (exec_idx, exec, pipeline) in pass
@@ -2275,6 +2421,32 @@ where
                         let view_info = view_info.as_ref().unwrap();
                         let buffer_view_info = view_info.as_buffer().unwrap();
 
+                        if matches!(
+                            descriptor_type,
+                            vk::DescriptorType::STORAGE_TEXEL_BUFFER
+                                | vk::DescriptorType::UNIFORM_TEXEL_BUFFER
+                        ) {
+                            let buffer_view = Buffer::view_ref(buffer, *buffer_view_info)?;
+
+                            if binding_offset == 0 {
+                                texel_buffer_writes.push(IndexWrite {
+                                    idx: texel_buffer_views.len(),
+                                    write: vk::WriteDescriptorSet {
+                                            dst_set: *descriptor_sets[descriptor_set_idx as usize],
+                                            dst_binding,
+                                            descriptor_type,
+                                            descriptor_count,
+                                            ..Default::default()
+                                        },
+                                    }
+                                );
+                            }
+
+                            texel_buffer_views.push(buffer_view);
+
+                            continue;
+                        }
+
                         if binding_offset == 0 {
                             buffer_writes.push(IndexWrite {
                                 idx: buffer_infos.len(),
@@ -2309,6 +2481,35 @@ where
                         }
 
                         accel_struct_infos.push(vk::WriteDescriptorSetAccelerationStructureKHR::builder().acceleration_structures(std::slice::from_ref(accel_struct)).build());
+                    } else if let Some(sampler) = bound_node.as_driver_sampler() {
+                        if descriptor_type != vk::DescriptorType::SAMPLER {
+                            warn!(
+                                "descriptor {descriptor_set_idx}.{dst_binding}[{binding_offset}] of pass \"{}\" is {descriptor_type:?}, which cannot be bound to a sampler",
+                                &pass.name
+                            );
+
+                            return Err(DriverError::InvalidData);
+                        }
+
+                        if binding_offset == 0 {
+                            image_writes.push(IndexWrite {
+                                idx: image_infos.len(),
+                                write: vk::WriteDescriptorSet {
+                                        dst_set: *descriptor_sets[descriptor_set_idx as usize],
+                                        dst_binding,
+                                        descriptor_type,
+                                        descriptor_count,
+                                        ..Default::default()
+                                    },
+                                }
+                            );
+                        }
+
+                        image_infos.push(vk::DescriptorImageInfo {
+                            image_layout: vk::ImageLayout::UNDEFINED,
+                            image_view: vk::ImageView::null(),
+                            sampler: **sampler,
+                        });
                     } else {
                         unimplemented!();
                     }
@@ -2400,9 +2601,13 @@ where
                 write.p_image_info = image_infos.as_ptr().add(idx);
                 write
             }));
+            descriptors.extend(texel_buffer_writes.drain(..).map(|IndexWrite { idx, mut write }| unsafe {
+                write.p_texel_buffer_view = texel_buffer_views.as_ptr().add(idx);
+                write
+            }));
 
             if !descriptors.is_empty() {
-                trace!("  writing {} descriptors ({} buffers, {} images)", descriptors.len(), buffer_infos.len(), image_infos.len());
+                trace!("  writing {} descriptors ({} buffers, {} images, {} texel buffers)", descriptors.len(), buffer_infos.len(), image_infos.len(), texel_buffer_views.len());
 
                 unsafe {
                     cmd_buf
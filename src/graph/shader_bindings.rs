@@ -0,0 +1,252 @@
+use {
+    super::{
+        info::Information, pass_ref::Access, AccelerationStructureLeaseNode,
+        AccelerationStructureNode, AnyAccelerationStructureNode, AnyBufferNode, AnyImageNode,
+        BufferLeaseNode, BufferNode, Descriptor, ImageLeaseNode, ImageNode, PipelinePassRef,
+        SamplerNode, SwapchainImageNode, View, ViewType,
+    },
+    crate::driver::{DescriptorBinding, DescriptorBindingMap, DescriptorInfo},
+    archery::SharedPointerKind,
+    std::{
+        error::Error,
+        fmt::{Display, Formatter},
+    },
+    vk_sync::AccessType,
+};
+
+/// A node which may be bound to a descriptor of a pipeline using its default view.
+pub trait DescriptorNode<P>: Copy + DescriptorNodeKind
+where
+    P: SharedPointerKind + Send + 'static,
+{
+    fn access_descriptor<'a, T>(
+        self,
+        pass: PipelinePassRef<'a, T, P>,
+        descriptor: Descriptor,
+        access: AccessType,
+    ) -> PipelinePassRef<'a, T, P>
+    where
+        T: Access;
+}
+
+impl<N, P> DescriptorNode<P> for N
+where
+    N: DescriptorNodeKind,
+    N: Information,
+    N: View<P>,
+    ViewType: From<<N as View<P>>::Information>,
+    <N as View<P>>::Information: From<<N as Information>::Info>,
+    <N as View<P>>::Subresource: From<<N as View<P>>::Information>,
+    P: SharedPointerKind + Send + 'static,
+{
+    fn access_descriptor<'a, T>(
+        self,
+        pass: PipelinePassRef<'a, T, P>,
+        descriptor: Descriptor,
+        access: AccessType,
+    ) -> PipelinePassRef<'a, T, P>
+    where
+        T: Access,
+    {
+        pass.access_descriptor(descriptor, self, access)
+    }
+}
+
+/// The kind of resource of a node, which must match the type of the descriptor it is bound to.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum NodeKind {
+    AccelerationStructure,
+    Buffer,
+    Image,
+    Sampler,
+}
+
+impl NodeKind {
+    /// Returns `true` if a node of this kind may be bound to the given descriptor.
+    pub fn is_compatible(self, descriptor_info: DescriptorInfo) -> bool {
+        match self {
+            Self::AccelerationStructure => {
+                matches!(descriptor_info, DescriptorInfo::AccelerationStructure(_))
+            }
+            Self::Buffer => matches!(
+                descriptor_info,
+                DescriptorInfo::StorageBuffer(..)
+                    | DescriptorInfo::StorageBufferDynamic(_)
+                    | DescriptorInfo::StorageTexelBuffer(..)
+                    | DescriptorInfo::UniformBuffer(_)
+                    | DescriptorInfo::UniformBufferDynamic(_)
+                    | DescriptorInfo::UniformTexelBuffer(_)
+            ),
+            Self::Image => matches!(
+                descriptor_info,
+                DescriptorInfo::CombinedImageSampler(..)
+                    | DescriptorInfo::InputAttachment(..)
+                    | DescriptorInfo::SampledImage(_)
+                    | DescriptorInfo::StorageImage(..)
+            ),
+            Self::Sampler => matches!(descriptor_info, DescriptorInfo::Sampler(_)),
+        }
+    }
+}
+
+impl Display for NodeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::AccelerationStructure => "acceleration structure",
+            Self::Buffer => "buffer",
+            Self::Image => "image",
+            Self::Sampler => "sampler",
+        })
+    }
+}
+
+/// A node type which has a known [`NodeKind`], regardless of its shared pointer type.
+pub trait DescriptorNodeKind {
+    const NODE_KIND: NodeKind;
+}
+
+macro_rules! descriptor_node_kind {
+    ($kind:ident: $($node:ident),+) => {
+        $(
+            impl<P> DescriptorNodeKind for $node<P> {
+                const NODE_KIND: NodeKind = NodeKind::$kind;
+            }
+        )+
+    };
+}
+
+descriptor_node_kind!(
+    AccelerationStructure: AccelerationStructureNode,
+    AccelerationStructureLeaseNode,
+    AnyAccelerationStructureNode
+);
+descriptor_node_kind!(Buffer: AnyBufferNode, BufferLeaseNode, BufferNode);
+descriptor_node_kind!(Image: AnyImageNode, ImageLeaseNode, ImageNode, SwapchainImageNode);
+descriptor_node_kind!(Sampler: SamplerNode);
+
+/// A group of nodes which are bound to the descriptors of a pipeline together.
+///
+/// Usually implemented using `#[derive(ShaderBindings)]` from the `screen-13-derive` crate, which
+/// removes the need to keep descriptor numbers in sync with shader code by hand.
+pub trait ShaderBindings<P>: ShaderDescriptors
+where
+    P: SharedPointerKind + Send + 'static,
+{
+    /// Binds each node to its descriptor of the given pass.
+    fn bind<'a, T>(self, pass: PipelinePassRef<'a, T, P>) -> PipelinePassRef<'a, T, P>
+    where
+        T: Access;
+}
+
+/// The error returned when a field of a [`ShaderBindings`] group does not match the descriptors
+/// reflected from the shaders of a pipeline.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ShaderBindingsError {
+    /// The descriptor of the field.
+    pub descriptor: Descriptor,
+
+    /// The name of the field, or its index for tuple structs.
+    pub field: &'static str,
+
+    /// The kind of node of the field.
+    pub node_kind: NodeKind,
+
+    /// The descriptor reflected from the pipeline shaders, or `None` if the pipeline has no such
+    /// binding.
+    pub reflected: Option<DescriptorInfo>,
+
+    /// `true` if the field writes to the descriptor.
+    pub write: bool,
+}
+
+impl Display for ShaderBindingsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (descriptor_set_idx, binding_idx, binding_offset) = self.descriptor.into_tuple();
+
+        write!(
+            f,
+            "field `{}` (set {descriptor_set_idx}, binding {binding_idx}",
+            self.field
+        )?;
+
+        if let Descriptor::ArrayBinding(..) = self.descriptor {
+            write!(f, ", index {binding_offset}")?;
+        }
+
+        let access = if self.write { "writable" } else { "readable" };
+        let node_kind = self.node_kind;
+
+        match self.reflected {
+            None => write!(
+                f,
+                "): expected a {access} {node_kind} descriptor, but the pipeline has no such \
+                binding"
+            ),
+            Some(reflected) if binding_offset >= reflected.binding_count() => write!(
+                f,
+                "): expected a {access} {node_kind} descriptor, reflected {reflected:?} has {} \
+                elements",
+                reflected.binding_count()
+            ),
+            Some(reflected) if !node_kind.is_compatible(reflected) => write!(
+                f,
+                "): expected a {access} {node_kind} descriptor, reflected {reflected:?} is not \
+                compatible"
+            ),
+            Some(reflected) => write!(
+                f,
+                "): expected a {access} {node_kind} descriptor, reflected {reflected:?} is \
+                read-only"
+            ),
+        }
+    }
+}
+
+impl Error for ShaderBindingsError {}
+
+/// The descriptors used by a [`ShaderBindings`] group.
+pub trait ShaderDescriptors {
+    /// Returns the name of each field of this type, its descriptor, its kind of node, and `true`
+    /// if it is written.
+    fn descriptors() -> Vec<(&'static str, Descriptor, NodeKind, bool)>;
+
+    /// Checks that each descriptor of this type exists in the given pipeline bindings (such as
+    /// `GraphicPipeline::descriptor_bindings`), that its type matches the kind of node, and that
+    /// written descriptors are writable.
+    fn validate(descriptor_bindings: &DescriptorBindingMap) -> Result<(), ShaderBindingsError> {
+        for (field, descriptor, node_kind, write) in Self::descriptors() {
+            let (descriptor_set_idx, binding_idx, binding_offset) = descriptor.into_tuple();
+            let err = |reflected| ShaderBindingsError {
+                descriptor,
+                field,
+                node_kind,
+                reflected,
+                write,
+            };
+            let descriptor_info = descriptor_bindings
+                .get(&DescriptorBinding(descriptor_set_idx, binding_idx))
+                .map(|(descriptor_info, _)| *descriptor_info)
+                .ok_or_else(|| err(None))?;
+
+            if binding_offset >= descriptor_info.binding_count()
+                || !node_kind.is_compatible(descriptor_info)
+            {
+                return Err(err(Some(descriptor_info)));
+            }
+
+            let is_writable = match descriptor_info {
+                DescriptorInfo::StorageBuffer(_, access)
+                | DescriptorInfo::StorageImage(_, access)
+                | DescriptorInfo::StorageTexelBuffer(_, access) => access.is_write(),
+                DescriptorInfo::StorageBufferDynamic(_) => true,
+                _ => false,
+            };
+
+            if write && !is_writable {
+                return Err(err(Some(descriptor_info)));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,231 @@
+use {
+    super::{
+        driver::{Device, DriverConfigBuilder, DriverError, Image, ImageInfo},
+        graph::{ImageBinding, ImageNode, RenderGraph},
+        HashPool,
+    },
+    archery::{SharedPointer, SharedPointerKind},
+    ash::vk,
+    log::{info, trace, warn},
+    std::marker::PhantomData,
+};
+
+/// Renders frames into an offscreen color image without creating a window or swapchain.
+///
+/// This allows rendering on machines without a display, such as continuous integration machines
+/// using a software Vulkan implementation.
+#[derive(Debug)]
+pub struct Headless<P>
+where
+    P: SharedPointerKind + Send,
+{
+    cache: HashPool<P>,
+    pub device: SharedPointer<Device<P>, P>,
+    dt: f32,
+    image: Option<ImageBinding<P>>,
+}
+
+impl<P> Headless<P>
+where
+    P: SharedPointerKind + Send,
+{
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(width: u32, height: u32) -> HeadlessBuilder<P> {
+        HeadlessBuilder {
+            __: PhantomData,
+            driver_cfg: DriverConfigBuilder::default().presentation(false),
+            dt: 1.0 / 60.0,
+            fmt: vk::Format::R8G8B8A8_UNORM,
+            height,
+            usage: vk::ImageUsageFlags::COLOR_ATTACHMENT
+                | vk::ImageUsageFlags::SAMPLED
+                | vk::ImageUsageFlags::TRANSFER_DST
+                | vk::ImageUsageFlags::TRANSFER_SRC,
+            width,
+        }
+    }
+
+    pub fn height(&self) -> u32 {
+        self.image().info.height
+    }
+
+    /// Returns the offscreen color image which frames are rendered into.
+    pub fn image(&self) -> &Image<P> {
+        self.image.as_ref().unwrap().get()
+    }
+
+    /// Renders frames until [`HeadlessContext::exit`] is called.
+    pub fn run<FrameFn>(&mut self, frame_fn: FrameFn) -> Result<(), DriverError>
+    where
+        FrameFn: FnMut(HeadlessContext<P>),
+        P: 'static,
+    {
+        self.run_frames(usize::MAX, frame_fn)
+    }
+
+    /// Renders up to `frame_count` frames, or fewer if [`HeadlessContext::exit`] is called.
+    ///
+    /// Once this function returns all rendering has completed and the offscreen image may be read.
+    pub fn run_frames<FrameFn>(
+        &mut self,
+        frame_count: usize,
+        mut frame_fn: FrameFn,
+    ) -> Result<(), DriverError>
+    where
+        FrameFn: FnMut(HeadlessContext<P>),
+        P: 'static,
+    {
+        let (width, height) = (self.width(), self.height());
+        let mut frame_idx = 0;
+        let mut will_exit = false;
+
+        while !will_exit && frame_idx < frame_count {
+            trace!("frame {frame_idx}");
+
+            let mut render_graph = RenderGraph::new();
+            let image = render_graph.bind_node(self.image.take().unwrap());
+
+            frame_fn(HeadlessContext {
+                device: &self.device,
+                dt: self.dt,
+                frame_idx,
+                height,
+                image,
+                render_graph: &mut render_graph,
+                will_exit: &mut will_exit,
+                width,
+            });
+
+            self.image = Some(render_graph.unbind_node(image));
+            render_graph.resolve().submit(&mut self.cache)?;
+
+            frame_idx += 1;
+        }
+
+        unsafe { self.device.device_wait_idle() }.map_err(|err| {
+            warn!("{err}");
+
+            DriverError::OutOfMemory
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.image().info.width
+    }
+}
+
+#[derive(Debug)]
+pub struct HeadlessBuilder<P> {
+    __: PhantomData<P>,
+    driver_cfg: DriverConfigBuilder,
+    dt: f32,
+    fmt: vk::Format,
+    height: u32,
+    usage: vk::ImageUsageFlags,
+    width: u32,
+}
+
+impl<P> HeadlessBuilder<P> {
+    pub fn configure<ConfigureFn>(mut self, configure_fn: ConfigureFn) -> Self
+    where
+        ConfigureFn: FnOnce(DriverConfigBuilder) -> DriverConfigBuilder,
+    {
+        self.driver_cfg = configure_fn(self.driver_cfg);
+        self
+    }
+
+    /// Enables Vulkan graphics debugging layers.
+    pub fn debug(mut self, debug: bool) -> Self {
+        self.driver_cfg = self.driver_cfg.debug(debug);
+        self
+    }
+
+    /// Sets the fixed time step, in seconds, given to each frame; `1/60` by default.
+    pub fn dt(mut self, dt: f32) -> Self {
+        self.dt = dt;
+        self
+    }
+
+    /// Sets the format of the offscreen color image; `R8G8B8A8_UNORM` by default.
+    pub fn fmt(mut self, fmt: vk::Format) -> Self {
+        self.fmt = fmt;
+        self
+    }
+
+    pub fn ray_tracing(mut self, ray_tracing: bool) -> Self {
+        self.driver_cfg = self.driver_cfg.ray_tracing(ray_tracing);
+        self
+    }
+
+    /// Sets the usage of the offscreen color image; by default it may be used as a color
+    /// attachment, sampled, or copied to and from.
+    pub fn usage(mut self, usage: vk::ImageUsageFlags) -> Self {
+        self.usage = usage;
+        self
+    }
+}
+
+impl<P> HeadlessBuilder<P>
+where
+    P: SharedPointerKind + Send + 'static,
+{
+    pub fn build(self) -> Result<Headless<P>, DriverError> {
+        let cfg = self
+            .driver_cfg
+            .build()
+            .map_err(|_| DriverError::InvalidData)?;
+        let device = SharedPointer::new(Device::new(cfg)?);
+        let image = Image::create(
+            &device,
+            ImageInfo::new_2d(self.fmt, self.width, self.height, self.usage),
+        )?;
+
+        info!(
+            "headless resolution: {}x{} ({:?})",
+            self.width, self.height, self.fmt
+        );
+
+        Ok(Headless {
+            cache: HashPool::new(&device),
+            device,
+            dt: self.dt,
+            image: Some(ImageBinding::new(image)),
+        })
+    }
+}
+
+/// The state of a single headless frame, similar to `FrameContext`.
+pub struct HeadlessContext<'a, P>
+where
+    P: SharedPointerKind,
+{
+    pub device: &'a SharedPointer<Device<P>, P>,
+
+    /// The fixed time step of each frame, in seconds.
+    pub dt: f32,
+
+    /// The zero-based index of this frame.
+    pub frame_idx: usize,
+
+    pub height: u32,
+
+    /// The offscreen color image which this frame is rendered into.
+    pub image: ImageNode<P>,
+
+    pub render_graph: &'a mut RenderGraph<P>,
+    pub will_exit: &'a mut bool,
+    pub width: u32,
+}
+
+impl<P> HeadlessContext<'_, P>
+where
+    P: SharedPointerKind,
+{
+    pub fn exit(&mut self) {
+        *self.will_exit = true;
+    }
+
+    pub fn render_aspect_ratio(&self) -> f32 {
+        self.width as f32 / self.height as f32
+    }
+}
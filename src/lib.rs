@@ -4,18 +4,22 @@
 pub mod driver;
 pub mod graph;
 
+mod capture;
 mod device_api;
 mod display;
 mod event_loop;
 mod frame;
 mod hash_pool;
+mod headless;
 mod input;
 
 pub use self::{
+    capture::{CaptureImage, CaptureSequence},
     display::{Display, DisplayError},
     event_loop::{run, EventLoop, EventLoopBuilder, FullscreenMode},
     frame::FrameContext,
     hash_pool::{HashPool, Lease},
+    headless::{Headless, HeadlessBuilder, HeadlessContext},
 };
 
 /// Things which are used in almost every single _Screen 13_ program.
@@ -51,11 +55,13 @@ pub mod prelude_all {
             AccelerationStructureLeaseNode, AccelerationStructureNode,
             AnyAccelerationStructureNode, AnyBufferBinding, AnyBufferNode, AnyImageBinding,
             AnyImageNode, BufferBinding, BufferLeaseBinding, BufferLeaseNode, BufferNode,
-            ImageBinding, ImageLeaseBinding, ImageLeaseNode, ImageNode, PassRef, PipelinePassRef,
-            RenderGraph, SwapchainImageNode,
+            DescriptorNameError, ImageBinding, ImageLeaseBinding, ImageLeaseNode, ImageNode,
+            NodeKind, PassRef, PipelinePassRef, RenderGraph, SamplerBinding, SamplerNode,
+            ShaderBindings, ShaderBindingsError, ShaderDescriptors, SwapchainImageNode,
         },
         prelude::*,
-        Display, DisplayError, HashPool, Lease,
+        CaptureImage, CaptureSequence, Display, DisplayError, HashPool, Headless, HeadlessBuilder,
+        HeadlessContext, Lease,
     }; // TODO: Expand!
 }
 
@@ -77,18 +83,26 @@ pub mod prelude_arc {
     pub type BufferBinding = all::BufferBinding<P>;
     pub type BufferLeaseNode = all::BufferLeaseNode<P>;
     pub type BufferNode = all::BufferNode<P>;
+    pub type BufferView = all::BufferView<P>;
     pub type ComputePipeline = all::ComputePipeline<P>;
+    pub type ComputePipelineVariants = all::ComputePipelineVariants<P>;
     pub type Device = all::Device<P>;
     pub type EventLoop = all::EventLoop<P>;
     pub type FrameContext<'a> = all::FrameContext<'a, P>;
     pub type GraphicPipeline = all::GraphicPipeline<P>;
+    pub type GraphicPipelineVariants = all::GraphicPipelineVariants<P>;
     pub type HashPool = all::HashPool<P>;
+    pub type Headless = all::Headless<P>;
+    pub type HeadlessContext<'a> = all::HeadlessContext<'a, P>;
     pub type Image = all::Image<P>;
     pub type ImageBinding = all::ImageBinding<P>;
     pub type ImageNode = all::ImageNode<P>;
     pub type PipelinePassRef<'a, T> = all::PipelinePassRef<'a, T, P>;
     pub type RayTracePipeline = all::RayTracePipeline<P>;
     pub type RenderGraph = all::RenderGraph<P>;
+    pub type Sampler = all::Sampler<P>;
+    pub type SamplerBinding = all::SamplerBinding<P>;
+    pub type SamplerNode = all::SamplerNode<P>;
     pub type SwapchainImage = all::SwapchainImage<P>;
 
     pub type Lease<T> = all::Lease<T, P>;
@@ -113,18 +127,26 @@ pub mod prelude_rc {
     pub type BufferBinding = all::BufferBinding<P>;
     pub type BufferLeaseNode = all::BufferLeaseNode<P>;
     pub type BufferNode = all::BufferNode<P>;
+    pub type BufferView = all::BufferView<P>;
     pub type ComputePipeline = all::ComputePipeline<P>;
+    pub type ComputePipelineVariants = all::ComputePipelineVariants<P>;
     pub type Device = all::Device<P>;
     pub type EventLoop = all::EventLoop<P>;
     pub type FrameContext<'a> = all::FrameContext<'a, P>;
     pub type GraphicPipeline = all::GraphicPipeline<P>;
+    pub type GraphicPipelineVariants = all::GraphicPipelineVariants<P>;
     pub type HashPool = all::HashPool<P>;
+    pub type Headless = all::Headless<P>;
+    pub type HeadlessContext<'a> = all::HeadlessContext<'a, P>;
     pub type Image = all::Image<P>;
     pub type ImageBinding = all::ImageBinding<P>;
     pub type ImageNode = all::ImageNode<P>;
     pub type PipelinePassRef<'a, T> = all::PipelinePassRef<'a, T, P>;
     pub type RayTracePipeline = all::RayTracePipeline<P>;
     pub type RenderGraph = all::RenderGraph<P>;
+    pub type Sampler = all::Sampler<P>;
+    pub type SamplerBinding = all::SamplerBinding<P>;
+    pub type SamplerNode = all::SamplerNode<P>;
     pub type SwapchainImage = all::SwapchainImage<P>;
 
     pub type Lease<T> = all::Lease<T, P>;